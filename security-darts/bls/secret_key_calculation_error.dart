@@ -1,8 +1,8 @@
-import 'package:blockben/secret/bls/bls_id.dart';
+import 'package:blockben/secret/pvsh/hiver.dart';
 
 class SharedKeyCalculationError {
-  final BlsId? senderId;
-  final BlsId? receiverId;
+  final HexString? senderId;
+  final HexString? receiverId;
   final String reason;
 
   SharedKeyCalculationError({
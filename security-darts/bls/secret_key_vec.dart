@@ -1,33 +0,0 @@
-import 'dart:ffi';
-
-import 'package:blockben/secret/bls/secret_key.dart';
-import 'package:blockben/secret/bls/structs/bls_secret_key.dart';
-import 'package:ffi/ffi.dart';
-
-class SecretKeyVec {
-  late final Pointer<BlsSecretKey> _pointer;
-  late final int _length;
-
-  SecretKeyVec(List<SecretKey> list) {
-    _length = list.length;
-    _pointer = calloc<BlsSecretKey>(length);
-    for (int i = 0; i < _length; i++) {
-      _pointer[i].v = list[i].pointer.ref.v;
-    }
-  }
-
-  Pointer<BlsSecretKey> get pointer => _pointer;
-  int get length => _length;
-
-  // Access operator to access individual keys
-  BlsSecretKey operator [](int index) => _pointer[index];
-
-  // Access operator to set individual keys
-  void operator []=(int index, BlsSecretKey key) {
-    _pointer[index] = key;
-  }
-
-  void dispose() {
-    calloc.free(_pointer);
-  }
-}
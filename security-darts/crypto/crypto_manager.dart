@@ -212,3 +212,4 @@ class CryptoManagerImpl implements CryptoManager {
 
 typedef SignatureHex = HexString;
 typedef PublicKeyHex = HexString;
+typedef SecretKeyHex = HexString;
@@ -1,5 +1,4 @@
 import 'package:blockben/model/constants.dart';
-import 'package:blockben/secret/bls/secret_key.dart';
 import 'package:blockben/secret/bls/utils/byte_array_utils.dart';
 import 'package:blockben/secret/crypto/crypto_manager.dart';
 import 'package:blockben/secret/crypto/hasher.dart';
@@ -14,10 +13,11 @@ abstract class SecretKeyManager {
 }
 
 class SecretKeyManagerImpl implements SecretKeyManager {
-  SecretKeyManagerImpl(this._cryptoManager, this._hasher);
+  SecretKeyManagerImpl(this._cryptoManager, this._hasher, this._hiver);
 
   final CryptoManager _cryptoManager;
   final Hasher _hasher;
+  final Hiver _hiver;
 
   @override
   Future<HexString> encrypt(
@@ -48,9 +48,8 @@ class SecretKeyManagerImpl implements SecretKeyManager {
       encryptedSecretKey.hexStrToByteArray(),
       finalHash.byteArrayToHexStr(),
     );
-    final secretKey = SecretKey()..deserialize(decrypted);
-    final publicKeyHex = secretKey.publicKey.serialize().byteArrayToHexStr();
+    final secretKeyHex = decrypted.byteArrayToHexStr();
 
-    return (secretKey.serialize().byteArrayToHexStr(), publicKeyHex);
+    return (secretKeyHex, _hiver.toPublicKey(secretKeyHex));
   }
 }
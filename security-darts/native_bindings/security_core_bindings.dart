@@ -0,0 +1,139 @@
+import 'dart:convert';
+import 'dart:ffi';
+import 'dart:io';
+
+import 'package:ffi/ffi.dart';
+
+typedef _SecurityCall = Pointer<Utf8> Function(Pointer<Utf8> request);
+typedef _SecurityFree = void Function(Pointer<Utf8> s);
+
+// Bindings for the Rust core (security-rs/include/security.h). Every call
+// takes a JSON request and returns {"ok": ...} or {"error": "..."}.
+class SecurityCoreBindings {
+  static final DynamicLibrary nativeLib = Platform.isAndroid
+      ? DynamicLibrary.open('libsecurity_capi.so')
+      : DynamicLibrary.process();
+
+  late final _SecurityFree _free = nativeLib
+      .lookupFunction<Void Function(Pointer<Utf8>), _SecurityFree>(
+          'security_string_free');
+
+  late final _SecurityCall _generateDeviceStorage =
+      _lookup('security_generate_device_storage');
+  late final _SecurityCall _generateKeyPair =
+      _lookup('security_generate_keypair');
+  late final _SecurityCall _generateId = _lookup('security_generate_id');
+  late final _SecurityCall _derivePublicKey =
+      _lookup('security_derive_public_key');
+  late final _SecurityCall _generateContribution =
+      _lookup('security_generate_contribution');
+  late final _SecurityCall _generateActorShare =
+      _lookup('security_generate_actor_share');
+  late final _SecurityCall _sign = _lookup('security_sign');
+  late final _SecurityCall _verify = _lookup('security_verify');
+  late final _SecurityCall _combineSignatures =
+      _lookup('security_combine_signatures');
+  late final _SecurityCall _pvshEncode = _lookup('security_pvsh_encode');
+  late final _SecurityCall _pvshVerify = _lookup('security_pvsh_verify');
+  late final _SecurityCall _pvshDecode = _lookup('security_pvsh_decode');
+
+  _SecurityCall _lookup(String name) {
+    return nativeLib
+        .lookupFunction<Pointer<Utf8> Function(Pointer<Utf8>), _SecurityCall>(
+            name);
+  }
+
+  dynamic _call(_SecurityCall function, Map<String, dynamic> request) {
+    final requestPointer = jsonEncode(request).toNativeUtf8();
+    try {
+      final responsePointer = function(requestPointer);
+      try {
+        final response =
+            jsonDecode(responsePointer.toDartString()) as Map<String, dynamic>;
+        if (response.containsKey('error')) {
+          throw Exception(response['error']);
+        }
+        return response['ok'];
+      } finally {
+        _free(responsePointer);
+      }
+    } finally {
+      calloc.free(requestPointer);
+    }
+  }
+
+  Map<String, dynamic> generateDeviceStorage(String name) =>
+      _call(_generateDeviceStorage, {'name': name});
+
+  Map<String, dynamic> generateKeyPair() => _call(_generateKeyPair, {});
+
+  String generateId() => _call(_generateId, {});
+
+  String derivePublicKey(String secretKey) =>
+      _call(_derivePublicKey, {'secret_key': secretKey});
+
+  Map<String, dynamic> generateContribution(
+    int threshold,
+    List<Map<String, dynamic>> members, {
+    String? oldSh,
+  }) =>
+      _call(_generateContribution, {
+        'threshold': threshold,
+        'members': members,
+        if (oldSh != null) 'old_sh': oldSh,
+      });
+
+  Map<String, dynamic> generateActorShare(
+    String actorId,
+    Map<String, dynamic> actorContract,
+    String myId,
+    String mySecretKey,
+  ) =>
+      _call(_generateActorShare, {
+        'actor_id': actorId,
+        'actor_contract': actorContract,
+        'my_id': myId,
+        'my_secret_key': mySecretKey,
+      });
+
+  String sign(String dataHex, String secretKey) =>
+      _call(_sign, {'data': dataHex, 'secret_key': secretKey});
+
+  bool verify(String dataHex, String signature, String publicKey) =>
+      _call(_verify, {
+        'data': dataHex,
+        'signature': signature,
+        'public_key': publicKey,
+      });
+
+  String combineSignatures(int threshold, List<Map<String, dynamic>> partials) =>
+      _call(_combineSignatures, {
+        'threshold': threshold,
+        'partials': partials,
+      });
+
+  String pvshEncode(String receiverId, String receiverPk, String sh) =>
+      _call(_pvshEncode, {
+        'receiver_id': receiverId,
+        'receiver_pk': receiverPk,
+        'sh': sh,
+      });
+
+  bool pvshVerify(
+          String receiverId, String receiverPk, String ph, String esh) =>
+      _call(_pvshVerify, {
+        'receiver_id': receiverId,
+        'receiver_pk': receiverPk,
+        'ph': ph,
+        'esh': esh,
+      });
+
+  String pvshDecode(
+          String receiverId, String receiverPk, String receiverSk, String esh) =>
+      _call(_pvshDecode, {
+        'receiver_id': receiverId,
+        'receiver_pk': receiverPk,
+        'receiver_sk': receiverSk,
+        'esh': esh,
+      });
+}
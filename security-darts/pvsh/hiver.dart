@@ -1,91 +1,70 @@
-import 'package:blockben/secret/bls/bls.dart';
-import 'package:blockben/secret/bls/bls_id.dart';
-import 'package:blockben/secret/bls/id_vec.dart';
-import 'package:blockben/secret/bls/public_key.dart';
-import 'package:blockben/secret/bls/secret_key.dart';
-import 'package:blockben/secret/bls/signature.dart';
-import 'package:blockben/secret/bls/signature_vec.dart';
 import 'package:blockben/secret/bls/utils/byte_array_utils.dart';
-import 'package:blockben/secret/crypto/hasher.dart';
-import 'package:blockben/secret/native_bindings/native_functions.dart';
+import 'package:blockben/secret/native_bindings/security_core_bindings.dart';
+import 'package:blockben/secret/pin/pin_manager.dart';
 
 abstract class Hiver {
-  PublicKey toPublicKey(HexString data);
-
-  SecretKey toSecretKey(HexString data);
-
   (HexString, HexString) generateKeyPairHex();
 
-  BlsId generateId();
+  HexString toPublicKey(HexString secretKey);
 
-  Signature sign({required String data, required HexString secretKey});
+  HexString generateId();
 
-  BlsId toId(HexString id);
+  HexString sign({required String data, required HexString secretKey});
 
-  Signature recoverSign(List<Signature> sigVec, List<BlsId> idVec);
+  HexString recoverSign(
+    int threshold,
+    List<HexString> sigVec,
+    List<HexString> idVec,
+  );
 
-  bool verify(String data, PublicKey publicKey, Signature signature);
+  bool verify(String data, HexString publicKey, HexString signature);
 }
 
+// Keys, ids and signatures are the hex encodings of the Rust core
+// (security-rs), which hashes the data with SHA-512 before signing.
 class HiverImpl implements Hiver {
-  final Hasher _hasher;
-
-  HiverImpl(this._hasher) {
-    BLS.blsInit(BLS12_381);
-  }
-
-  @override
-  PublicKey toPublicKey(HexString data) =>
-      PublicKey()..deserialize(data.hexStrToByteArray());
+  final SecurityCoreBindings _core;
 
-  @override
-  SecretKey toSecretKey(HexString data) =>
-      SecretKey()..deserialize(data.hexStrToByteArray());
+  HiverImpl(this._core);
 
   @override
   (HexString, HexString) generateKeyPairHex() {
-    final secretKey = SecretKey();
-    secretKey.setByCSPRNG();
-    return (
-      secretKey.serialize().byteArrayToHexStr(),
-      secretKey.publicKey.serialize().byteArrayToHexStr()
-    );
+    final keyPair = _core.generateKeyPair();
+    return (keyPair['secret_key'] as String, keyPair['public_key'] as String);
   }
 
-  /// blsIdByCSPRNG does not exist. We have to generate a
-  /// Cryptographycally secure pseudo random number into id.v
-  /// Because the SecretKey contains the same { MclBnFr v } ,
-  /// we use the method of SecretKey, but renamed.
   @override
-  BlsId generateId() {
-    final id = BlsId();
-    nativeFunctions.blsId.blsIdByCSPRNG(id.pointer);
-    return id;
-  }
-
-  static const BLS12_381 = 5;
+  HexString toPublicKey(HexString secretKey) =>
+      _core.derivePublicKey(secretKey);
 
   @override
-  Signature sign({required String data, required HexString secretKey}) {
-    final sk = SecretKey()..deserialize(secretKey.hexStrToByteArray());
-    final hash = _hasher.sha512Hash(data);
-    return sk.sign(hash);
-  }
+  HexString generateId() => _core.generateId();
 
   @override
-  BlsId toId(HexString id) {
-    return BlsId()..deserialize(id.hexStrToByteArray());
-  }
+  HexString sign({required String data, required HexString secretKey}) =>
+      _core.sign(data.toByteArray().byteArrayToHexStr(), secretKey);
 
   @override
-  Signature recoverSign(List<Signature> sigVec, List<BlsId> idVec) {
-    return Signature()..recover(SignatureVec(sigVec), IdVec(idVec));
+  HexString recoverSign(
+    int threshold,
+    List<HexString> sigVec,
+    List<HexString> idVec,
+  ) {
+    if (sigVec.length != idVec.length) {
+      throw ArgumentError('Every signature needs the id of its signer');
+    }
+    return _core.combineSignatures(threshold, [
+      for (var i = 0; i < sigVec.length; i++) {'id': idVec[i], 'sig': sigVec[i]},
+    ]);
   }
 
   @override
-  bool verify(String data, PublicKey publicKey, Signature signature) {
-    return signature.verify(publicKey, _hasher.sha512Hash(data));
-  }
+  bool verify(String data, HexString publicKey, HexString signature) =>
+      _core.verify(
+        data.toByteArray().byteArrayToHexStr(),
+        signature,
+        publicKey,
+      );
 }
 
 typedef HexString = String;
@@ -1,5 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
-use security::{generate_device_storage, generate_contribution, generate_id_hex, generate_keypair_hex, init_bls, Member};
+use security::{generate_device_storage, generate_contribution, generate_id_hex, generate_keypair_hex, init_bls, Member, MemberRole};
 use std::sync::Once;
 
 static INIT: Once = Once::new();
@@ -32,6 +32,7 @@ fn bench_generate_contribution(c: &mut Criterion) {
             .map(|_| Member {
                 id: generate_id_hex(),
                 pm: generate_keypair_hex().public_key,
+                role: MemberRole::Device,
             })
             .collect();
 
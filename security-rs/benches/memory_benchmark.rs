@@ -1,6 +1,6 @@
 /// This is a manual benchmark to measure memory usage
 /// Run with: cargo run --release --bin memory_benchmark
-use security::{generate_contribution, generate_device_storage, generate_id_hex, generate_keypair_hex, init_bls, Member};
+use security::{generate_contribution, generate_device_storage, generate_id_hex, generate_keypair_hex, init_bls, Member, MemberRole};
 use std::time::Instant;
 
 fn print_memory_info() {
@@ -65,6 +65,7 @@ fn main() {
             .map(|_| Member {
                 id: generate_id_hex(),
                 pm: generate_keypair_hex().public_key,
+                role: MemberRole::Device,
             })
             .collect();
 
@@ -1,75 +1,316 @@
 use std::env;
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+const BLS_LIB: &str = "bls384_256";
+const MCL_LIB: &str = "mcl";
+const BLS_REPOSITORY: &str = "https://github.com/herumi/bls.git";
+// herumi/bls commit built by default; its gitlink pins the mcl submodule.
+// BLS_FETCH_REV overrides it. Must be replaced with a real commit id.
+const BLS_PINNED_REV: &str = "0000000000000000000000000000000000000000";
+
 fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed=BLS_LIB_DIR");
+    println!("cargo:rerun-if-env-changed=BLS_SOURCE_DIR");
+    println!("cargo:rerun-if-env-changed=BLS_FETCH_REV");
+    println!("cargo:rerun-if-env-changed=CARGO_NET_OFFLINE");
+
+    #[cfg(feature = "napi")]
+    napi_build::setup();
+
+    if env::var_os("CARGO_FEATURE_MCL").is_none() {
+        return;
+    }
+
     let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
-    let bls_dir = out_dir.join("bls");
-
-    // Clone bls library (which includes mcl as a submodule)
-    if !bls_dir.exists() {
-        println!("cargo:warning=Cloning bls library (includes mcl)...");
-        let status = Command::new("git")
-            .args(&[
-                "clone",
-                "--depth",
-                "1",
-                "--recursive",
-                "https://github.com/herumi/bls.git",
-                bls_dir.to_str().unwrap(),
-            ])
-            .status()
-            .expect("Failed to clone bls repository");
 
-        if !status.success() {
-            panic!("Failed to clone bls repository");
+    // 1. Prebuilt static libraries
+    if let Some(lib_dir) = env::var_os("BLS_LIB_DIR") {
+        let lib_dir = PathBuf::from(lib_dir);
+        for lib in [BLS_LIB, MCL_LIB] {
+            let path = lib_dir.join(format!("lib{}.a", lib));
+            if !path.exists() {
+                fail(&format!(
+                    "BLS_LIB_DIR is set to {} but {} does not exist",
+                    lib_dir.display(),
+                    path.display()
+                ));
+            }
+            println!("cargo:rerun-if-changed={}", path.display());
         }
+        link_static(&[lib_dir]);
+        return;
     }
 
-    // Build bls using make (this also builds mcl as a dependency)
-    println!("cargo:warning=Building bls library with BLS12-381 support...");
+    // 2. System installation discovered via pkg-config
+    if probe_pkg_config() {
+        link_cpp_runtime();
+        return;
+    }
 
-    let status = Command::new("make")
-        .current_dir(&bls_dir)
-        .env("CFLAGS", "-O3 -DNDEBUG -fPIC")
-        .env("CXXFLAGS", "-O3 -DNDEBUG -fPIC")
-        .args(&["-j", "lib/libbls384_256.a"])
-        .status()
-        .expect("Failed to build bls");
+    let revision = env::var("BLS_FETCH_REV").unwrap_or_else(|_| BLS_PINNED_REV.to_string());
+    if !is_commit_id(&revision) {
+        fail(&format!(
+            "BLS_FETCH_REV must be a full 40-character herumi/bls commit id, got `{}`",
+            revision
+        ));
+    }
+    if revision.bytes().all(|b| b == b'0') {
+        fail(&format!(
+            "BLS_PINNED_REV in build.rs is a placeholder; set it (or BLS_FETCH_REV) to a \
+             herumi/bls commit id.\n{}",
+            ALTERNATIVES
+        ));
+    }
 
-    if !status.success() {
-        panic!("Failed to build bls library");
+    let bls_dir = match env::var_os("BLS_SOURCE_DIR") {
+        // 3. Local herumi/bls checkout, which must be at the pinned revision
+        Some(source_dir) => {
+            let source_dir = PathBuf::from(source_dir);
+            if !is_bls_source(&source_dir) {
+                fail(&format!(
+                    "BLS_SOURCE_DIR={} is not a herumi/bls checkout with the mcl submodule",
+                    source_dir.display()
+                ));
+            }
+            watch_source(&source_dir);
+            verify_checkout(&source_dir, &revision);
+            let build_dir = out_dir.join("bls");
+            copy_dir(&source_dir, &build_dir)
+                .unwrap_or_else(|e| fail(&format!("Failed to copy bls sources: {}", e)));
+            build_dir
+        }
+        // 4. Download of the pinned revision
+        None => fetch_bls(&out_dir.join(format!("bls-{}", revision)), &revision),
+    };
+
+    build_bls(&bls_dir);
+    link_static(&[bls_dir.join("lib"), bls_dir.join("mcl").join("lib")]);
+}
+
+const ALTERNATIVES: &str = "Without network access, provide one of:\n  \
+     - BLS_LIB_DIR=<dir containing libbls384_256.a and libmcl.a>\n  \
+     - a pkg-config installation of bls384_256 and mcl\n  \
+     - BLS_SOURCE_DIR=<herumi/bls checkout at the pinned commit, with the mcl submodule>\n\
+     or build with `--no-default-features --features pure-rust`.";
+
+fn fail(message: &str) -> ! {
+    panic!("\n\nsecurity build error: {}\n\n", message);
+}
+
+fn is_commit_id(revision: &str) -> bool {
+    revision.len() == 40 && revision.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn is_bls_source(dir: &Path) -> bool {
+    dir.join("Makefile").exists() && dir.join("mcl").join("Makefile").exists()
+}
+
+fn watch_source(dir: &Path) {
+    for path in [
+        "Makefile",
+        "src",
+        "include",
+        "mcl/Makefile",
+        "mcl/src",
+        "mcl/include",
+    ] {
+        println!("cargo:rerun-if-changed={}", dir.join(path).display());
     }
+}
 
-    // Also build mcl library (for low-level operations)
-    let mcl_dir = bls_dir.join("mcl");
-    println!("cargo:warning=Building mcl library...");
+fn probe_pkg_config() -> bool {
+    let mut config = pkg_config::Config::new();
+    config.statik(true).cargo_metadata(false);
+    let libraries = match config
+        .probe(BLS_LIB)
+        .and_then(|bls| Ok((bls, config.probe(MCL_LIB)?)))
+    {
+        Ok((bls, mcl)) => [bls, mcl],
+        Err(_) => return false,
+    };
 
-    let status = Command::new("make")
-        .current_dir(&mcl_dir)
-        .env("CFLAGS", "-O3 -DNDEBUG -fPIC")
-        .env("CXXFLAGS", "-O3 -DNDEBUG -fPIC")
-        .args(&["-j", "lib/libmcl.a"])
-        .status()
-        .expect("Failed to build mcl");
+    for library in &libraries {
+        for path in &library.link_paths {
+            println!("cargo:rustc-link-search=native={}", path.display());
+        }
+    }
+    for library in &libraries {
+        for lib in &library.libs {
+            println!("cargo:rustc-link-lib={}", lib);
+        }
+    }
+    true
+}
 
-    if !status.success() {
-        panic!("Failed to build mcl library");
+fn fetch_bls(bls_dir: &Path, revision: &str) -> PathBuf {
+    let offline = env::var("CARGO_NET_OFFLINE")
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(false);
+    if offline {
+        fail(&format!(
+            "Building herumi/bls {} requires network access but CARGO_NET_OFFLINE is set.\n{}",
+            revision, ALTERNATIVES
+        ));
     }
 
-    // Link to both libraries (order matters: bls first, then mcl)
-    println!("cargo:rustc-link-search=native={}/lib", bls_dir.display());
-    println!("cargo:rustc-link-search=native={}/lib", mcl_dir.display());
-    println!("cargo:rustc-link-lib=static=bls384_256");
-    println!("cargo:rustc-link-lib=static=mcl");
+    if !is_bls_source(bls_dir) {
+        println!(
+            "cargo:warning=Fetching herumi/bls at {} (includes mcl)...",
+            revision
+        );
+        // Leftovers of an interrupted clone would make `git clone` refuse
+        if bls_dir.exists() {
+            fs::remove_dir_all(bls_dir).unwrap_or_else(|e| {
+                fail(&format!("Failed to remove {}: {}", bls_dir.display(), e))
+            });
+        }
+        let cloned = Command::new("git")
+            .args(["clone", BLS_REPOSITORY])
+            .arg(bls_dir)
+            .status()
+            .is_ok_and(|status| status.success());
+        if !cloned {
+            fail(&format!(
+                "Failed to clone {}. Offline builds should set CARGO_NET_OFFLINE=true \
+                 (cargo's `--offline` flag is not visible to build scripts).\n{}",
+                BLS_REPOSITORY, ALTERNATIVES
+            ));
+        }
+        run(
+            Command::new("git")
+                .current_dir(bls_dir)
+                .args(["checkout", "--detach", revision]),
+            "check out pinned bls revision",
+        );
+        run(
+            Command::new("git").current_dir(bls_dir).args([
+                "submodule",
+                "update",
+                "--init",
+                "--recursive",
+            ]),
+            "check out mcl submodule",
+        );
+    }
+    verify_checkout(bls_dir, revision);
+
+    bls_dir.to_path_buf()
+}
+
+// The commit id pins the bls tree and, through its gitlink, the mcl submodule;
+// make sure that is what ended up on disk.
+fn verify_checkout(bls_dir: &Path, revision: &str) {
+    let head = output(
+        Command::new("git")
+            .current_dir(bls_dir)
+            .args(["rev-parse", "HEAD"]),
+        "read bls revision",
+    );
+    if !head.eq_ignore_ascii_case(revision) {
+        fail(&format!(
+            "{} is at {} instead of the pinned bls revision {}",
+            bls_dir.display(),
+            head,
+            revision
+        ));
+    }
+
+    let pinned_mcl = output(
+        Command::new("git")
+            .current_dir(bls_dir)
+            .args(["rev-parse", "HEAD:mcl"]),
+        "read pinned mcl revision",
+    );
+    let mcl = output(
+        Command::new("git")
+            .current_dir(bls_dir.join("mcl"))
+            .args(["rev-parse", "HEAD"]),
+        "read mcl revision",
+    );
+    if mcl != pinned_mcl {
+        fail(&format!(
+            "mcl submodule is at {} but bls {} pins {}",
+            mcl, revision, pinned_mcl
+        ));
+    }
+}
+
+fn build_bls(bls_dir: &Path) {
+    // Building bls also requires the mcl headers from the submodule
+    println!("cargo:warning=Building bls library with BLS12-381 support...");
+    run(
+        Command::new("make")
+            .current_dir(bls_dir)
+            .env("CFLAGS", "-O3 -DNDEBUG -fPIC")
+            .env("CXXFLAGS", "-O3 -DNDEBUG -fPIC")
+            .args(["-j", "lib/libbls384_256.a"]),
+        "build bls library",
+    );
+
+    println!("cargo:warning=Building mcl library...");
+    run(
+        Command::new("make")
+            .current_dir(bls_dir.join("mcl"))
+            .env("CFLAGS", "-O3 -DNDEBUG -fPIC")
+            .env("CXXFLAGS", "-O3 -DNDEBUG -fPIC")
+            .args(["-j", "lib/libmcl.a"]),
+        "build mcl library",
+    );
+}
+
+fn link_static(search_dirs: &[PathBuf]) {
+    for dir in search_dirs {
+        println!("cargo:rustc-link-search=native={}", dir.display());
+    }
+    // Order matters: bls first, then mcl
+    println!("cargo:rustc-link-lib=static={}", BLS_LIB);
+    println!("cargo:rustc-link-lib=static={}", MCL_LIB);
+    link_cpp_runtime();
+}
 
-    // Also need to link C++ standard library
-    if cfg!(target_os = "macos") {
+fn link_cpp_runtime() {
+    if env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("macos") {
         println!("cargo:rustc-link-lib=dylib=c++");
     } else {
         println!("cargo:rustc-link-lib=dylib=stdc++");
     }
+}
+
+fn run(command: &mut Command, what: &str) {
+    let status = command
+        .status()
+        .unwrap_or_else(|e| fail(&format!("Failed to {}: {}", what, e)));
+    if !status.success() {
+        fail(&format!("Failed to {} ({})", what, status));
+    }
+}
+
+fn output(command: &mut Command, what: &str) -> String {
+    let output = command
+        .output()
+        .unwrap_or_else(|e| fail(&format!("Failed to {}: {}", what, e)));
+    if !output.status.success() {
+        fail(&format!("Failed to {} ({})", what, output.status));
+    }
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
 
-    // Tell cargo to rerun if the bls directory changes
-    println!("cargo:rerun-if-changed={}", bls_dir.display());
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        if entry.file_name() == ".git" {
+            continue;
+        }
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
 }
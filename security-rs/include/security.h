@@ -0,0 +1,112 @@
+#ifndef SECURITY_H
+#define SECURITY_H
+
+/* Generated by cbindgen from src/capi.rs. Do not edit by hand. */
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Generates a new virtual device storage.
+//
+// Request: `{"name": string}`. Every function in this module returns a
+// JSON envelope `{"ok": <result>}` or `{"error": string}` that must be
+// released with `security_string_free`.
+//
+// # Safety
+//
+// `request` must be null or a valid NUL-terminated string.
+char *security_generate_device_storage(const char *request);
+
+// Request: `{}`. Returns `{"secret_key": hex, "public_key": hex}`.
+//
+// # Safety
+//
+// `request` must be null or a valid NUL-terminated string.
+char *security_generate_keypair(const char *request);
+
+// Request: `{}`. Returns a random member id hex.
+//
+// # Safety
+//
+// `request` must be null or a valid NUL-terminated string.
+char *security_generate_id(const char *request);
+
+// Request: `{"secret_key": hex}`. Returns the G2 public key hex.
+//
+// # Safety
+//
+// `request` must be null or a valid NUL-terminated string.
+char *security_derive_public_key(const char *request);
+
+// Request: `{"threshold": number, "members": [Member], "old_sh"?: hex}`.
+// With `old_sh` the contribution reshares that secret instead of a fresh one.
+//
+// # Safety
+//
+// `request` must be null or a valid NUL-terminated string.
+char *security_generate_contribution(const char *request);
+
+// Request: `{"actor_id", "actor_contract": ActorContract, "my_id", "my_secret_key"}`.
+//
+// # Safety
+//
+// `request` must be null or a valid NUL-terminated string.
+char *security_generate_actor_share(const char *request);
+
+// Request: `{"data": hex, "secret_key": hex}`. Returns the signature hex.
+//
+// # Safety
+//
+// `request` must be null or a valid NUL-terminated string.
+char *security_sign(const char *request);
+
+// Request: `{"data": hex, "signature": hex, "public_key": hex}`. Returns a bool.
+//
+// # Safety
+//
+// `request` must be null or a valid NUL-terminated string.
+char *security_verify(const char *request);
+
+// Request: `{"threshold": number, "partials": [{"id": hex, "sig": hex}]}`.
+// Returns the group signature hex.
+//
+// # Safety
+//
+// `request` must be null or a valid NUL-terminated string.
+char *security_combine_signatures(const char *request);
+
+// Request: `{"receiver_id", "receiver_pk", "sh"}`. Returns the encrypted share.
+//
+// # Safety
+//
+// `request` must be null or a valid NUL-terminated string.
+char *security_pvsh_encode(const char *request);
+
+// Request: `{"receiver_id", "receiver_pk", "ph", "esh"}`. Returns a bool.
+//
+// # Safety
+//
+// `request` must be null or a valid NUL-terminated string.
+char *security_pvsh_verify(const char *request);
+
+// Request: `{"receiver_id", "receiver_pk", "receiver_sk", "esh"}`. Returns the share hex.
+//
+// # Safety
+//
+// `request` must be null or a valid NUL-terminated string.
+char *security_pvsh_decode(const char *request);
+
+// Releases a string returned by any `security_*` function.
+//
+// # Safety
+//
+// `s` must be null or a pointer returned by this library that has not
+// been freed yet.
+void security_string_free(char *s);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* SECURITY_H */
@@ -0,0 +1,4 @@
+//! Builds the `security` C API (`security::capi`) as a shared and a static
+//! library.
+
+pub use security::capi::*;
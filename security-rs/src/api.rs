@@ -0,0 +1,584 @@
+//! Typed client for the backend device and actor-contract endpoints.
+//!
+//! Models serialize like the TS `common.ts` interfaces (camelCase JSON).
+
+use crate::crypto::sign;
+use crate::crypto::threshold::generate_contribution;
+use crate::types::{
+    ActorContract, ActorShareData, Contribution, Member, MemberRole, ReceivedContribution,
+    VirtualDeviceStorage,
+};
+use crate::virtual_device::VirtualDevice;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::thread;
+use std::time::Duration;
+
+pub const DEVICE_REGISTRATION_ROLE_CODE: &str = "DEVICEREG";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateDeviceRequest {
+    pub name: String,
+    pub id: String,
+    pub pm: String,
+    pub shared_id: String,
+    pub shared_pm: String,
+    pub model: String,
+    pub os_name: String,
+    pub os_version: String,
+    pub push_token: String,
+}
+
+impl CreateDeviceRequest {
+    /// Fills the identity fields from the device storage; model, OS and push
+    /// token are left empty for the caller.
+    pub fn from_storage(storage: &VirtualDeviceStorage) -> Self {
+        CreateDeviceRequest {
+            name: storage.name.clone(),
+            id: storage.id.clone(),
+            pm: storage.pm.clone(),
+            shared_id: storage.shared_device_data.id.clone(),
+            shared_pm: storage.shared_device_data.pm.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContributeDevicePayload {
+    pub contract_id: String,
+    pub sender_id: String,
+    pub contribution: Contribution,
+    pub device_registration_token: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InstructionMode {
+    pub code: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InitiationRole {
+    pub code: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitiationContext {
+    pub acting_actor_id: String,
+    pub hat_actor_id: String,
+    pub share_code: String,
+    pub role_code: String,
+    pub owner_actor_id: String,
+    pub subject_actor_id: String,
+    pub instruction_mode: InstructionMode,
+    pub initiation_role: InitiationRole,
+}
+
+/// The signed part of an operation: `url` must be the endpoint it is posted to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationRequest<P = ContributeDevicePayload> {
+    pub payload: P,
+    pub initiation_context: InitiationContext,
+    pub message_id: String,
+    pub url: String,
+}
+
+impl<P: Serialize> OperationRequest<P> {
+    /// Signs the canonical JSON of the request, as the backend verifies it.
+    pub fn sign(self, secret_key: &str) -> Result<InputOperationRequest<P>, ApiError> {
+        let data_json =
+            stringify_with_converter(&self).map_err(|e| ApiError::Decode(e.to_string()))?;
+        let signature = sign(data_json.as_bytes(), secret_key).map_err(ApiError::Crypto)?;
+        Ok(InputOperationRequest {
+            data: self,
+            signature,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputOperationRequest<P = ContributeDevicePayload> {
+    pub data: OperationRequest<P>,
+    pub signature: String,
+}
+
+/// Serializes like TS `JSON.stringify(value, JSONConverter)`: numbers become
+/// strings, integers with a trailing `.0`.
+pub fn stringify_with_converter(value: &impl Serialize) -> Result<String, serde_json::Error> {
+    let json_value = serde_json::to_value(value)?;
+    let converted = convert_numbers_to_decimal_strings(&json_value);
+    serde_json::to_string(&converted)
+}
+
+fn convert_numbers_to_decimal_strings(value: &Value) -> Value {
+    match value {
+        Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Value::String(format!("{}.0", i))
+            } else if let Some(u) = n.as_u64() {
+                Value::String(format!("{}.0", u))
+            } else if let Some(f) = n.as_f64() {
+                if f.fract() == 0.0 {
+                    Value::String(format!("{}.0", f as i64))
+                } else {
+                    Value::String(f.to_string())
+                }
+            } else {
+                value.clone()
+            }
+        }
+        Value::Array(arr) => {
+            Value::Array(arr.iter().map(convert_numbers_to_decimal_strings).collect())
+        }
+        Value::Object(obj) => Value::Object(
+            obj.iter()
+                .map(|(k, v)| (k.clone(), convert_numbers_to_decimal_strings(v)))
+                .collect(),
+        ),
+        _ => value.clone(),
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ActorShareResponse {
+    pub share_code: String,
+    pub subject_actor_id: Option<String>,
+    pub subject_actor_type: Option<String>,
+    pub hat_id: Option<String>,
+    pub from_actor_id: Option<String>,
+    pub role_code: Option<String>,
+    pub to_actor_id: Option<String>,
+    pub owner_actor_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemberResponse {
+    pub id: String,
+    pub pm: String,
+    #[serde(default, skip_serializing_if = "MemberRole::is_device")]
+    pub role: MemberRole,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceivedContributionResponse {
+    pub sender_id: String,
+    pub contribution: Contribution,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ActorContractResponse {
+    pub id: Option<String>,
+    pub threshold: Option<usize>,
+    pub contributors: Vec<MemberResponse>,
+    pub new_members: Vec<MemberResponse>,
+    pub contributions: Vec<ReceivedContributionResponse>,
+    pub actor_share: Option<ActorShareResponse>,
+    pub is_finalized: bool,
+}
+
+impl ActorContractResponse {
+    pub fn members(&self) -> Vec<Member> {
+        self.new_members
+            .iter()
+            .map(|member| Member {
+                id: member.id.clone(),
+                pm: member.pm.clone(),
+                role: member.role,
+            })
+            .collect()
+    }
+
+    /// Converts to the contract type the threshold functions take.
+    pub fn to_actor_contract(&self) -> Result<ActorContract, ApiError> {
+        let threshold = self
+            .threshold
+            .ok_or_else(|| ApiError::MissingField("threshold".to_string()))?;
+        let share = self
+            .actor_share
+            .as_ref()
+            .ok_or_else(|| ApiError::MissingField("actorShare".to_string()))?;
+
+        Ok(ActorContract {
+            threshold,
+            new_members: self.members(),
+            contributions: self
+                .contributions
+                .iter()
+                .map(|received| ReceivedContribution {
+                    sender_id: received.sender_id.clone(),
+                    contribution: received.contribution.clone(),
+                })
+                .collect(),
+            actor_share: ActorShareData {
+                share_code: share.share_code.clone(),
+                subject_actor_id: share.subject_actor_id.clone().unwrap_or_default(),
+                hat_id: share.hat_id.clone().unwrap_or_default(),
+                from_actor_id: share.from_actor_id.clone().unwrap_or_default(),
+                to_actor_id: share.to_actor_id.clone().unwrap_or_default(),
+                owner_actor_id: share.owner_actor_id.clone().unwrap_or_default(),
+            },
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum OperationStatus {
+    Pending,
+    Success,
+    Error,
+    Expired,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignatureChainResponse {
+    pub actor_share_code: String,
+    pub id: String,
+    pub ph: String,
+    pub sig: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sigs: Option<Vec<SignatureChainResponse>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationResponse<T> {
+    pub uid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_operation: Option<String>,
+    pub state: OperationStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<T>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature_chain: Option<SignatureChainResponse>,
+    #[serde(default)]
+    pub updated_by: String,
+    #[serde(default)]
+    pub created_at: i64,
+}
+
+impl<T> OperationResponse<T> {
+    /// The result of a successful or pending operation.
+    pub fn into_result(self) -> Result<T, ApiError> {
+        match self.state {
+            OperationStatus::Error | OperationStatus::Expired => Err(ApiError::Operation {
+                uid: self.uid,
+                state: self.state,
+            }),
+            _ => self
+                .result
+                .ok_or_else(|| ApiError::MissingField("result".to_string())),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ApiError {
+    /// The request hit the configured timeout.
+    Timeout,
+    Transport(String),
+    /// The server answered with a non-2xx status.
+    Status {
+        status: u16,
+        body: String,
+    },
+    Decode(String),
+    /// The operation was accepted but ended in `ERROR` or `EXPIRED`.
+    Operation {
+        uid: String,
+        state: OperationStatus,
+    },
+    MissingField(String),
+    /// Computing the contribution, actor share or signature failed.
+    Crypto(String),
+}
+
+impl ApiError {
+    /// Whether resending the same request may succeed.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ApiError::Timeout | ApiError::Transport(_) => true,
+            ApiError::Status { status, .. } => *status == 429 || *status >= 500,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiError::Timeout => write!(f, "Request timed out"),
+            ApiError::Transport(reason) => write!(f, "Request failed: {}", reason),
+            ApiError::Status { status, body } => {
+                write!(f, "Server responded with {}: {}", status, body)
+            }
+            ApiError::Decode(reason) => write!(f, "Invalid response: {}", reason),
+            ApiError::Operation { uid, state } => {
+                write!(f, "Operation {} ended with state {:?}", uid, state)
+            }
+            ApiError::MissingField(field) => write!(f, "Response is missing {}", field),
+            ApiError::Crypto(reason) => write!(f, "Crypto error: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl From<ureq::Error> for ApiError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Timeout(_) => ApiError::Timeout,
+            ureq::Error::StatusCode(status) => ApiError::Status {
+                status,
+                body: String::new(),
+            },
+            e => ApiError::Transport(e.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiConfig {
+    /// Base URL including any path prefix, e.g. `http://localhost:3000/api`.
+    pub base_url: String,
+    /// Extra headers sent with every request.
+    pub headers: Vec<(String, String)>,
+    /// Limit for the whole request, from connect to reading the body.
+    pub timeout: Option<Duration>,
+    pub connect_timeout: Option<Duration>,
+}
+
+impl ApiConfig {
+    pub fn new(base_url: &str) -> Self {
+        ApiConfig {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            headers: Vec::new(),
+            timeout: Some(Duration::from_secs(30)),
+            connect_timeout: Some(Duration::from_secs(10)),
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+pub struct ApiClient {
+    config: ApiConfig,
+    agent: ureq::Agent,
+}
+
+impl ApiClient {
+    pub fn new(config: ApiConfig) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(config.timeout)
+            .timeout_connect(config.connect_timeout)
+            .build()
+            .new_agent();
+        ApiClient { config, agent }
+    }
+
+    pub fn config(&self) -> &ApiConfig {
+        &self.config
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.config.base_url, path)
+    }
+
+    pub fn contribute_device_url(&self, contract_id: &str) -> String {
+        self.url(&format!(
+            "/actor-contracts/{}/contribute-device",
+            contract_id
+        ))
+    }
+
+    fn post<B: Serialize, R: DeserializeOwned>(&self, url: &str, body: &B) -> Result<R, ApiError> {
+        let mut request = self.agent.post(url);
+        for (name, value) in &self.config.headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+
+        let mut response = request.send_json(body)?;
+        let status = response.status().as_u16();
+        let body = response.body_mut().read_to_string()?;
+        if !(200..300).contains(&status) {
+            return Err(ApiError::Status { status, body });
+        }
+        serde_json::from_str(&body).map_err(|e| ApiError::Decode(e.to_string()))
+    }
+
+    /// `POST /devices`: registers the device and returns its registration contract.
+    pub fn create_device(
+        &self,
+        request: &CreateDeviceRequest,
+    ) -> Result<OperationResponse<ActorContractResponse>, ApiError> {
+        self.post(&self.url("/devices"), request)
+    }
+
+    /// `POST /actor-contracts/{id}/contribute-device`.
+    pub fn contribute_device(
+        &self,
+        contract_id: &str,
+        request: &InputOperationRequest,
+    ) -> Result<OperationResponse<ActorContractResponse>, ApiError> {
+        self.post(&self.contribute_device_url(contract_id), request)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RegistrationOptions {
+    pub model: String,
+    pub os_name: String,
+    pub os_version: String,
+    pub push_token: String,
+    /// Id of the contribute operation; a random one is used when `None`.
+    /// Retries always resend the same id so the backend can deduplicate.
+    pub message_id: Option<String>,
+    /// Attempts at submitting the contribution, including the first.
+    pub max_attempts: u32,
+    pub retry_delay: Duration,
+}
+
+impl Default for RegistrationOptions {
+    fn default() -> Self {
+        RegistrationOptions {
+            model: String::new(),
+            os_name: "Other".to_string(),
+            os_version: String::new(),
+            push_token: String::new(),
+            message_id: None,
+            max_attempts: 3,
+            retry_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RegisteredDevice {
+    /// The device, holding the registration actor share (and its share code)
+    /// only once the contract `is_finalized` or every member has contributed.
+    pub device: VirtualDevice,
+    pub operation: OperationResponse<ActorContractResponse>,
+}
+
+fn random_message_id() -> String {
+    let mut bytes = [0u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Registers `device` with the backend: creates it, contributes to the
+/// returned registration contract and submits the contribution signed with
+/// the shared device key.
+///
+/// `device` is left untouched so its keys survive a failed registration; the
+/// updated copy is returned.
+pub fn register_device(
+    client: &ApiClient,
+    device: &VirtualDevice,
+    options: &RegistrationOptions,
+) -> Result<RegisteredDevice, ApiError> {
+    let storage = &device.storage;
+    let create_device_input = CreateDeviceRequest {
+        model: options.model.clone(),
+        os_name: options.os_name.clone(),
+        os_version: options.os_version.clone(),
+        push_token: options.push_token.clone(),
+        ..CreateDeviceRequest::from_storage(storage)
+    };
+    let actor_contract = client.create_device(&create_device_input)?.into_result()?;
+
+    let contract_id = actor_contract
+        .id
+        .clone()
+        .ok_or_else(|| ApiError::MissingField("id".to_string()))?;
+    let threshold = actor_contract
+        .threshold
+        .ok_or_else(|| ApiError::MissingField("threshold".to_string()))?;
+    let contribution =
+        generate_contribution(threshold, &actor_contract.members()).map_err(ApiError::Crypto)?;
+
+    let shared_id = &storage.shared_device_data.id;
+    let operation_request = OperationRequest {
+        payload: ContributeDevicePayload {
+            contract_id: contract_id.clone(),
+            sender_id: storage.id.clone(),
+            contribution,
+            // Only accepted by backends running in dev mode
+            device_registration_token: shared_id.clone(),
+        },
+        initiation_context: InitiationContext {
+            acting_actor_id: storage.id.clone(),
+            hat_actor_id: shared_id.clone(),
+            share_code: device.registration_share_code(),
+            role_code: DEVICE_REGISTRATION_ROLE_CODE.to_string(),
+            owner_actor_id: shared_id.clone(),
+            subject_actor_id: shared_id.clone(),
+            instruction_mode: InstructionMode {
+                code: "IN_PERSON".to_string(),
+            },
+            initiation_role: InitiationRole {
+                code: "OWN_NAME".to_string(),
+            },
+        },
+        message_id: options.message_id.clone().unwrap_or_else(random_message_id),
+        url: client.contribute_device_url(&contract_id),
+    };
+    let input_operation = operation_request.sign(&storage.shared_device_data.sm)?;
+
+    let mut attempt = 1;
+    let operation = loop {
+        match client.contribute_device(&contract_id, &input_operation) {
+            Err(e) if e.is_retryable() && attempt < options.max_attempts => {
+                attempt += 1;
+                thread::sleep(options.retry_delay);
+            }
+            result => break result?,
+        }
+    };
+
+    if matches!(
+        operation.state,
+        OperationStatus::Error | OperationStatus::Expired
+    ) {
+        return Err(ApiError::Operation {
+            uid: operation.uid,
+            state: operation.state,
+        });
+    }
+
+    let mut device = device.clone();
+    if let Some(contract) = &operation.result {
+        let complete = !contract.new_members.is_empty()
+            && contract.contributions.len() == contract.new_members.len();
+        if contract.is_finalized || complete {
+            let actor_contract = contract.to_actor_contract()?;
+            let actor_id = device.storage.id.clone();
+            device
+                .generate_actor_share(&actor_id, &actor_contract)
+                .map_err(ApiError::Crypto)?;
+        }
+    }
+
+    Ok(RegisteredDevice { device, operation })
+}
@@ -0,0 +1,309 @@
+use crate::crypto::threshold::{
+    combine_partial_signatures, generate_actor_share, generate_contribution_internal,
+};
+use crate::crypto::utils::{bytes_to_hex, hex_to_bytes};
+use crate::crypto::{
+    derive_public_key_g2, deserialize_fr, deserialize_g2, generate_id_hex, generate_keypair_hex,
+    get_g2_generator, init_bls, pvsh_decode_g2, pvsh_encode_g2, pvsh_verify_g2, serialize_fr,
+    serialize_g2, sign, verify,
+};
+use crate::device::generate_device_storage;
+use crate::types::{ActorContract, Member, PartialSignature};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, UnwindSafe};
+use std::ptr;
+use std::sync::Once;
+
+static INIT: Once = Once::new();
+
+#[derive(Deserialize)]
+struct EmptyRequest {}
+
+#[derive(Deserialize)]
+struct GenerateDeviceStorageRequest {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct DerivePublicKeyRequest {
+    secret_key: String,
+}
+
+#[derive(Deserialize)]
+struct GenerateContributionRequest {
+    threshold: usize,
+    members: Vec<Member>,
+    #[serde(default)]
+    old_sh: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct GenerateActorShareRequest {
+    actor_id: String,
+    actor_contract: ActorContract,
+    my_id: String,
+    my_secret_key: String,
+}
+
+#[derive(Deserialize)]
+struct SignRequest {
+    data: String,
+    secret_key: String,
+}
+
+#[derive(Deserialize)]
+struct VerifyRequest {
+    data: String,
+    signature: String,
+    public_key: String,
+}
+
+#[derive(Deserialize)]
+struct CombineSignaturesRequest {
+    threshold: usize,
+    partials: Vec<PartialSignature>,
+}
+
+#[derive(Deserialize)]
+struct PvshEncodeRequest {
+    receiver_id: String,
+    receiver_pk: String,
+    sh: String,
+}
+
+#[derive(Deserialize)]
+struct PvshVerifyRequest {
+    receiver_id: String,
+    receiver_pk: String,
+    ph: String,
+    esh: String,
+}
+
+#[derive(Deserialize)]
+struct PvshDecodeRequest {
+    receiver_id: String,
+    receiver_pk: String,
+    receiver_sk: String,
+    esh: String,
+}
+
+fn call<Req, Res, F>(request: *const c_char, f: F) -> *mut c_char
+where
+    Req: DeserializeOwned,
+    Res: Serialize,
+    F: FnOnce(Req) -> Result<Res, String> + UnwindSafe,
+{
+    let result = catch_unwind(|| {
+        INIT.call_once(init_bls);
+        let request = unsafe { read_request::<Req>(request) }?;
+        let response = f(request)?;
+        serde_json::to_value(response).map_err(|e| format!("Failed to encode response: {}", e))
+    })
+    .unwrap_or_else(|_| Err("Internal error".to_string()));
+
+    let envelope = match result {
+        Ok(value) => json!({ "ok": value }),
+        Err(e) => json!({ "error": e }),
+    };
+    into_c_string(envelope)
+}
+
+unsafe fn read_request<Req: DeserializeOwned>(request: *const c_char) -> Result<Req, String> {
+    if request.is_null() {
+        return Err("Request must not be null".to_string());
+    }
+    let request = CStr::from_ptr(request)
+        .to_str()
+        .map_err(|_| "Request is not valid UTF-8".to_string())?;
+    serde_json::from_str(request).map_err(|e| format!("Invalid request: {}", e))
+}
+
+fn into_c_string(value: Value) -> *mut c_char {
+    // serde_json escapes interior NUL bytes, so this cannot fail
+    CString::new(value.to_string())
+        .map(CString::into_raw)
+        .unwrap_or(ptr::null_mut())
+}
+
+/// Generates a new virtual device storage.
+///
+/// Request: `{"name": string}`. Every function in this module returns a
+/// JSON envelope `{"ok": <result>}` or `{"error": string}` that must be
+/// released with `security_string_free`.
+///
+/// # Safety
+///
+/// `request` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn security_generate_device_storage(request: *const c_char) -> *mut c_char {
+    call(request, |req: GenerateDeviceStorageRequest| {
+        Ok(generate_device_storage(&req.name))
+    })
+}
+
+/// Request: `{}`. Returns `{"secret_key": hex, "public_key": hex}`.
+///
+/// # Safety
+///
+/// `request` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn security_generate_keypair(request: *const c_char) -> *mut c_char {
+    call(request, |_: EmptyRequest| Ok(generate_keypair_hex()))
+}
+
+/// Request: `{}`. Returns a random member id hex.
+///
+/// # Safety
+///
+/// `request` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn security_generate_id(request: *const c_char) -> *mut c_char {
+    call(request, |_: EmptyRequest| Ok(generate_id_hex()))
+}
+
+/// Request: `{"secret_key": hex}`. Returns the G2 public key hex.
+///
+/// # Safety
+///
+/// `request` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn security_derive_public_key(request: *const c_char) -> *mut c_char {
+    call(request, |req: DerivePublicKeyRequest| {
+        let sk = deserialize_fr(&hex_to_bytes(&req.secret_key)?)?;
+        Ok(bytes_to_hex(&serialize_g2(&derive_public_key_g2(&sk))))
+    })
+}
+
+/// Request: `{"threshold": number, "members": [Member], "old_sh"?: hex}`.
+/// With `old_sh` the contribution reshares that secret instead of a fresh one.
+///
+/// # Safety
+///
+/// `request` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn security_generate_contribution(request: *const c_char) -> *mut c_char {
+    call(request, |req: GenerateContributionRequest| {
+        let old_sh = match &req.old_sh {
+            Some(sh) => Some(deserialize_fr(&hex_to_bytes(sh)?)?),
+            None => None,
+        };
+        generate_contribution_internal(req.threshold, &req.members, old_sh.as_ref())
+    })
+}
+
+/// Request: `{"actor_id", "actor_contract": ActorContract, "my_id", "my_secret_key"}`.
+///
+/// # Safety
+///
+/// `request` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn security_generate_actor_share(request: *const c_char) -> *mut c_char {
+    call(request, |req: GenerateActorShareRequest| {
+        generate_actor_share(
+            &req.actor_id,
+            &req.actor_contract,
+            &req.my_id,
+            &req.my_secret_key,
+        )
+    })
+}
+
+/// Request: `{"data": hex, "secret_key": hex}`. Returns the signature hex.
+///
+/// # Safety
+///
+/// `request` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn security_sign(request: *const c_char) -> *mut c_char {
+    call(request, |req: SignRequest| {
+        sign(&hex_to_bytes(&req.data)?, &req.secret_key)
+    })
+}
+
+/// Request: `{"data": hex, "signature": hex, "public_key": hex}`. Returns a bool.
+///
+/// # Safety
+///
+/// `request` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn security_verify(request: *const c_char) -> *mut c_char {
+    call(request, |req: VerifyRequest| {
+        verify(&hex_to_bytes(&req.data)?, &req.signature, &req.public_key)
+    })
+}
+
+/// Request: `{"threshold": number, "partials": [{"id": hex, "sig": hex}]}`.
+/// Returns the group signature hex.
+///
+/// # Safety
+///
+/// `request` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn security_combine_signatures(request: *const c_char) -> *mut c_char {
+    call(request, |req: CombineSignaturesRequest| {
+        combine_partial_signatures(req.threshold, &req.partials)
+    })
+}
+
+/// Request: `{"receiver_id", "receiver_pk", "sh"}`. Returns the encrypted share.
+///
+/// # Safety
+///
+/// `request` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn security_pvsh_encode(request: *const c_char) -> *mut c_char {
+    call(request, |req: PvshEncodeRequest| {
+        let receiver_id = deserialize_fr(&hex_to_bytes(&req.receiver_id)?)?;
+        let receiver_pk = deserialize_g2(&hex_to_bytes(&req.receiver_pk)?)?;
+        let sh = deserialize_fr(&hex_to_bytes(&req.sh)?)?;
+        pvsh_encode_g2(&receiver_id, &receiver_pk, &sh, &get_g2_generator())
+    })
+}
+
+/// Request: `{"receiver_id", "receiver_pk", "ph", "esh"}`. Returns a bool.
+///
+/// # Safety
+///
+/// `request` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn security_pvsh_verify(request: *const c_char) -> *mut c_char {
+    call(request, |req: PvshVerifyRequest| {
+        let receiver_id = deserialize_fr(&hex_to_bytes(&req.receiver_id)?)?;
+        let receiver_pk = deserialize_g2(&hex_to_bytes(&req.receiver_pk)?)?;
+        let ph = deserialize_g2(&hex_to_bytes(&req.ph)?)?;
+        let helper_g2 = get_g2_generator();
+        Ok(pvsh_verify_g2(&receiver_id, &receiver_pk, &ph, &req.esh, &helper_g2).is_ok())
+    })
+}
+
+/// Request: `{"receiver_id", "receiver_pk", "receiver_sk", "esh"}`. Returns the share hex.
+///
+/// # Safety
+///
+/// `request` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn security_pvsh_decode(request: *const c_char) -> *mut c_char {
+    call(request, |req: PvshDecodeRequest| {
+        let receiver_id = deserialize_fr(&hex_to_bytes(&req.receiver_id)?)?;
+        let receiver_pk = deserialize_g2(&hex_to_bytes(&req.receiver_pk)?)?;
+        let receiver_sk = deserialize_fr(&hex_to_bytes(&req.receiver_sk)?)?;
+        let sh = pvsh_decode_g2(&receiver_id, &receiver_pk, &receiver_sk, &req.esh)?;
+        Ok(bytes_to_hex(&serialize_fr(&sh)))
+    })
+}
+
+/// Releases a string returned by any `security_*` function.
+///
+/// # Safety
+///
+/// `s` must be null or a pointer returned by this library that has not
+/// been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn security_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
@@ -0,0 +1,579 @@
+#![allow(non_snake_case, clippy::missing_safety_doc)]
+
+use super::{CurveBackend, PureRust};
+use crate::crypto::ffi::*;
+use std::os::raw::{c_int, c_void};
+use std::slice;
+
+type B = PureRust;
+
+unsafe fn bytes<'a>(buf: *const c_void, size: usize) -> &'a [u8] {
+    if buf.is_null() {
+        return &[];
+    }
+    slice::from_raw_parts(buf as *const u8, size)
+}
+
+unsafe fn write_bytes(buf: *mut c_void, max_size: usize, data: &[u8]) -> usize {
+    if data.len() > max_size {
+        return 0;
+    }
+    std::ptr::copy_nonoverlapping(data.as_ptr(), buf as *mut u8, data.len());
+    data.len()
+}
+
+// mcl's arithmetic entry points have no error return. A malformed operand
+// yields a malformed result instead, which the serialize (0), compare (0)
+// and `-1`-returning entry points then reject.
+trait Malformed {
+    fn malformed() -> Self;
+}
+
+const MALFORMED_FP: mclBnFp = mclBnFp { d: [u64::MAX; 6] };
+const MALFORMED_FP2: mclBnFp2 = mclBnFp2 {
+    d: [MALFORMED_FP; 2],
+};
+
+impl Malformed for mclBnFr {
+    fn malformed() -> Self {
+        mclBnFr { d: [u64::MAX; 4] }
+    }
+}
+
+impl Malformed for mclBnG1 {
+    fn malformed() -> Self {
+        mclBnG1 {
+            x: MALFORMED_FP,
+            y: MALFORMED_FP,
+            z: MALFORMED_FP,
+        }
+    }
+}
+
+impl Malformed for mclBnG2 {
+    fn malformed() -> Self {
+        mclBnG2 {
+            x: MALFORMED_FP2,
+            y: MALFORMED_FP2,
+            z: MALFORMED_FP2,
+        }
+    }
+}
+
+impl Malformed for mclBnGT {
+    fn malformed() -> Self {
+        mclBnGT {
+            d: [MALFORMED_FP; 12],
+        }
+    }
+}
+
+fn checked<T: Malformed>(value: Option<T>) -> T {
+    value.unwrap_or_else(T::malformed)
+}
+
+fn evaluate_polynomial(coefficients: &[mclBnFr], x: &mclBnFr) -> Option<mclBnFr> {
+    let mut result = B::fr_from_int(0);
+    for c in coefficients.iter().rev() {
+        result = B::fr_add(&B::fr_mul(&result, x)?, c)?;
+    }
+    Some(result)
+}
+
+fn lagrange_coefficients_at_zero(ids: &[mclBnFr]) -> Option<Vec<mclBnFr>> {
+    let mut coefficients = Vec::with_capacity(ids.len());
+    for (i, xi) in ids.iter().enumerate() {
+        if B::fr_is_zero(xi)? {
+            return None;
+        }
+        let mut numerator = B::fr_from_int(1);
+        let mut denominator = B::fr_from_int(1);
+        for (j, xj) in ids.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let delta = B::fr_sub(xj, xi)?;
+            if B::fr_is_zero(&delta)? {
+                return None;
+            }
+            numerator = B::fr_mul(&numerator, xj)?;
+            denominator = B::fr_mul(&denominator, &delta)?;
+        }
+        coefficients.push(B::fr_div(&numerator, &denominator)?);
+    }
+    Some(coefficients)
+}
+
+fn ids_of(ids: &[BlsId]) -> Vec<mclBnFr> {
+    ids.iter().map(|id| id.v).collect()
+}
+
+pub unsafe fn mclBn_init(curve: c_int, _compiledTimeVar: c_int) -> c_int {
+    if curve == MCL_BLS12_381 && B::init() {
+        0
+    } else {
+        -1
+    }
+}
+
+pub unsafe fn blsInit(curve: c_int, compiledTimeVar: c_int) -> c_int {
+    mclBn_init(curve, compiledTimeVar)
+}
+
+pub unsafe fn mclBnFr_setInt(y: *mut mclBnFr, x: i64) {
+    *y = B::fr_from_int(x);
+}
+
+pub unsafe fn mclBnFr_setByCSPRNG(x: *mut mclBnFr) -> c_int {
+    match B::fr_random() {
+        Some(fr) => {
+            *x = fr;
+            0
+        }
+        None => -1,
+    }
+}
+
+pub unsafe fn mclBnFr_setHashOf(x: *mut mclBnFr, buf: *const c_void, bufSize: usize) -> c_int {
+    match B::fr_hash_of(bytes(buf, bufSize)) {
+        Some(fr) => {
+            *x = fr;
+            0
+        }
+        None => -1,
+    }
+}
+
+pub unsafe fn mclBnFr_deserialize(x: *mut mclBnFr, buf: *const c_void, bufSize: usize) -> usize {
+    match B::fr_deserialize(bytes(buf, bufSize)) {
+        Some(fr) => {
+            *x = fr;
+            FR_SIZE
+        }
+        None => 0,
+    }
+}
+
+pub unsafe fn mclBnFr_serialize(buf: *mut c_void, maxBufSize: usize, x: *const mclBnFr) -> usize {
+    write_bytes(buf, maxBufSize, &B::fr_serialize(&*x))
+}
+
+pub unsafe fn mclBnFr_setLittleEndian(
+    x: *mut mclBnFr,
+    buf: *const c_void,
+    bufSize: usize,
+) -> c_int {
+    match B::fr_set_little_endian(bytes(buf, bufSize)) {
+        Some(fr) => {
+            *x = fr;
+            0
+        }
+        None => -1,
+    }
+}
+
+pub unsafe fn mclBnFr_getLittleEndian(
+    buf: *mut c_void,
+    maxBufSize: usize,
+    x: *const mclBnFr,
+) -> usize {
+    write_bytes(buf, maxBufSize, &B::fr_get_little_endian(&*x))
+}
+
+pub unsafe fn mclBnFr_isEqual(x: *const mclBnFr, y: *const mclBnFr) -> c_int {
+    B::fr_is_equal(&*x, &*y).unwrap_or(false) as c_int
+}
+
+pub unsafe fn mclBnFr_isZero(x: *const mclBnFr) -> c_int {
+    B::fr_is_zero(&*x).unwrap_or(false) as c_int
+}
+
+pub unsafe fn mclBnFr_add(z: *mut mclBnFr, x: *const mclBnFr, y: *const mclBnFr) {
+    *z = checked(B::fr_add(&*x, &*y));
+}
+
+pub unsafe fn mclBnFr_sub(z: *mut mclBnFr, x: *const mclBnFr, y: *const mclBnFr) {
+    *z = checked(B::fr_sub(&*x, &*y));
+}
+
+pub unsafe fn mclBnFr_mul(z: *mut mclBnFr, x: *const mclBnFr, y: *const mclBnFr) {
+    *z = checked(B::fr_mul(&*x, &*y));
+}
+
+pub unsafe fn mclBnFr_div(z: *mut mclBnFr, x: *const mclBnFr, y: *const mclBnFr) {
+    *z = checked(B::fr_div(&*x, &*y));
+}
+
+pub unsafe fn mclBnG1_deserialize(x: *mut mclBnG1, buf: *const c_void, bufSize: usize) -> usize {
+    match B::g1_deserialize(bytes(buf, bufSize)) {
+        Some(p) => {
+            *x = p;
+            G1_SIZE
+        }
+        None => 0,
+    }
+}
+
+pub unsafe fn mclBnG1_serialize(buf: *mut c_void, maxBufSize: usize, x: *const mclBnG1) -> usize {
+    write_bytes(buf, maxBufSize, &B::g1_serialize(&*x))
+}
+
+pub unsafe fn mclBnG1_hashAndMapTo(x: *mut mclBnG1, buf: *const c_void, bufSize: usize) -> c_int {
+    match B::g1_hash_and_map_to(bytes(buf, bufSize)) {
+        Some(p) => {
+            *x = p;
+            0
+        }
+        None => -1,
+    }
+}
+
+pub unsafe fn mclBnFp_setLittleEndianMod(
+    x: *mut mclBnFp,
+    buf: *const c_void,
+    bufSize: usize,
+) -> c_int {
+    match B::fp_set_little_endian_mod(bytes(buf, bufSize)) {
+        Some(fp) => {
+            *x = fp;
+            0
+        }
+        None => -1,
+    }
+}
+
+pub unsafe fn mclBnFp_mapToG1(y: *mut mclBnG1, x: *const mclBnFp) -> c_int {
+    match B::g1_map_to(&*x) {
+        Some(p) => {
+            *y = p;
+            0
+        }
+        None => -1,
+    }
+}
+
+pub unsafe fn mclBnG1_mul(z: *mut mclBnG1, x: *const mclBnG1, y: *const mclBnFr) {
+    *z = checked(B::g1_mul(&*x, &*y));
+}
+
+pub unsafe fn mclBnG1_add(z: *mut mclBnG1, x: *const mclBnG1, y: *const mclBnG1) {
+    *z = checked(B::g1_add(&*x, &*y));
+}
+
+pub unsafe fn mclBnG1_isEqual(x: *const mclBnG1, y: *const mclBnG1) -> c_int {
+    B::g1_is_equal(&*x, &*y).unwrap_or(false) as c_int
+}
+
+pub unsafe fn mclBnG1_normalize(y: *mut mclBnG1, x: *const mclBnG1) {
+    *y = checked(B::g1_normalize(&*x));
+}
+
+pub unsafe fn mclBnFp_serialize(buf: *mut c_void, maxBufSize: usize, x: *const mclBnFp) -> usize {
+    write_bytes(buf, maxBufSize, &B::fp_serialize(&*x))
+}
+
+pub unsafe fn mclBnG2_deserialize(x: *mut mclBnG2, buf: *const c_void, bufSize: usize) -> usize {
+    match B::g2_deserialize(bytes(buf, bufSize)) {
+        Some(p) => {
+            *x = p;
+            G2_SIZE
+        }
+        None => 0,
+    }
+}
+
+pub unsafe fn mclBnG2_serialize(buf: *mut c_void, maxBufSize: usize, x: *const mclBnG2) -> usize {
+    write_bytes(buf, maxBufSize, &B::g2_serialize(&*x))
+}
+
+pub unsafe fn mclBnG2_mul(z: *mut mclBnG2, x: *const mclBnG2, y: *const mclBnFr) {
+    *z = checked(B::g2_mul(&*x, &*y));
+}
+
+pub unsafe fn mclBnG2_add(z: *mut mclBnG2, x: *const mclBnG2, y: *const mclBnG2) {
+    *z = checked(B::g2_add(&*x, &*y));
+}
+
+pub unsafe fn mclBnG2_isEqual(x: *const mclBnG2, y: *const mclBnG2) -> c_int {
+    B::g2_is_equal(&*x, &*y).unwrap_or(false) as c_int
+}
+
+pub unsafe fn mclBnG2_isZero(x: *const mclBnG2) -> c_int {
+    B::g2_is_zero(&*x).unwrap_or(false) as c_int
+}
+
+pub unsafe fn mclBnGT_mul(z: *mut mclBnGT, x: *const mclBnGT, y: *const mclBnGT) {
+    *z = checked(B::gt_mul(&*x, &*y));
+}
+
+pub unsafe fn mclBnGT_isEqual(x: *const mclBnGT, y: *const mclBnGT) -> c_int {
+    B::gt_is_equal(&*x, &*y).unwrap_or(false) as c_int
+}
+
+pub unsafe fn mclBnGT_serialize(buf: *mut c_void, maxBufSize: usize, x: *const mclBnGT) -> usize {
+    write_bytes(buf, maxBufSize, &B::gt_serialize(&*x))
+}
+
+pub unsafe fn mclBn_pairing(z: *mut mclBnGT, x: *const mclBnG1, y: *const mclBnG2) {
+    *z = checked(B::pairing(&*x, &*y));
+}
+
+pub unsafe fn mclBn_FrEvaluatePolynomial(
+    out: *mut mclBnFr,
+    c_vec: *const mclBnFr,
+    c_size: usize,
+    x: *const mclBnFr,
+) -> c_int {
+    if c_size == 0 {
+        return -1;
+    }
+    match evaluate_polynomial(slice::from_raw_parts(c_vec, c_size), &*x) {
+        Some(value) => {
+            *out = value;
+            0
+        }
+        None => -1,
+    }
+}
+
+pub unsafe fn mclBn_FrLagrangeInterpolation(
+    out: *mut mclBnFr,
+    x_vec: *const mclBnFr,
+    y_vec: *const mclBnFr,
+    k: usize,
+) -> c_int {
+    if k == 0 {
+        return -1;
+    }
+    let ys = slice::from_raw_parts(y_vec, k);
+    let interpolate = || {
+        let coefficients = lagrange_coefficients_at_zero(slice::from_raw_parts(x_vec, k))?;
+        let mut result = B::fr_from_int(0);
+        for (c, y) in coefficients.iter().zip(ys) {
+            result = B::fr_add(&result, &B::fr_mul(c, y)?)?;
+        }
+        Some(result)
+    };
+    match interpolate() {
+        Some(result) => {
+            *out = result;
+            0
+        }
+        None => -1,
+    }
+}
+
+pub unsafe fn blsSecretKeySetByCSPRNG(sec: *mut BlsSecretKey) -> c_int {
+    mclBnFr_setByCSPRNG(&mut (*sec).v)
+}
+
+pub unsafe fn blsSecretKeySerialize(
+    buf: *mut c_void,
+    maxBufSize: usize,
+    sec: *const BlsSecretKey,
+) -> usize {
+    mclBnFr_serialize(buf, maxBufSize, &(*sec).v)
+}
+
+pub unsafe fn blsSecretKeyDeserialize(
+    sec: *mut BlsSecretKey,
+    buf: *const c_void,
+    bufSize: usize,
+) -> usize {
+    mclBnFr_deserialize(&mut (*sec).v, buf, bufSize)
+}
+
+pub unsafe fn blsSecretKeyShare(
+    sec: *mut BlsSecretKey,
+    msk: *const BlsSecretKey,
+    k: usize,
+    id: *const BlsId,
+) -> c_int {
+    if k == 0 {
+        return -1;
+    }
+    let coefficients: Vec<mclBnFr> = slice::from_raw_parts(msk, k).iter().map(|s| s.v).collect();
+    match evaluate_polynomial(&coefficients, &(*id).v) {
+        Some(share) => {
+            (*sec).v = share;
+            0
+        }
+        None => -1,
+    }
+}
+
+pub unsafe fn blsSecretKeyRecover(
+    sec: *mut BlsSecretKey,
+    secVec: *const BlsSecretKey,
+    idVec: *const BlsId,
+    n: usize,
+) -> c_int {
+    if n == 0 {
+        return -1;
+    }
+    let secrets = slice::from_raw_parts(secVec, n);
+    let recover = || {
+        let coefficients = lagrange_coefficients_at_zero(&ids_of(slice::from_raw_parts(idVec, n)))?;
+        let mut result = B::fr_from_int(0);
+        for (c, s) in coefficients.iter().zip(secrets) {
+            result = B::fr_add(&result, &B::fr_mul(c, &s.v)?)?;
+        }
+        Some(result)
+    };
+    match recover() {
+        Some(result) => {
+            (*sec).v = result;
+            0
+        }
+        None => -1,
+    }
+}
+
+pub unsafe fn blsGetPublicKey(pub_key: *mut BlsPublicKey, sec_key: *const BlsSecretKey) {
+    (*pub_key).v = checked(B::g2_mul(&B::g2_generator(), &(*sec_key).v));
+}
+
+pub unsafe fn blsPublicKeySerialize(
+    buf: *mut c_void,
+    maxBufSize: usize,
+    pub_key: *const BlsPublicKey,
+) -> usize {
+    mclBnG2_serialize(buf, maxBufSize, &(*pub_key).v)
+}
+
+pub unsafe fn blsPublicKeyDeserialize(
+    pub_key: *mut BlsPublicKey,
+    buf: *const c_void,
+    bufSize: usize,
+) -> usize {
+    mclBnG2_deserialize(&mut (*pub_key).v, buf, bufSize)
+}
+
+pub unsafe fn blsPublicKeyShare(
+    pub_key: *mut BlsPublicKey,
+    mpk: *const BlsPublicKey,
+    k: usize,
+    id: *const BlsId,
+) -> c_int {
+    if k == 0 {
+        return -1;
+    }
+    let coefficients = slice::from_raw_parts(mpk, k);
+    let share = || {
+        let mut result = coefficients[k - 1].v;
+        for c in coefficients[..k - 1].iter().rev() {
+            result = B::g2_add(&B::g2_mul(&result, &(*id).v)?, &c.v)?;
+        }
+        Some(result)
+    };
+    match share() {
+        Some(result) => {
+            (*pub_key).v = result;
+            0
+        }
+        None => -1,
+    }
+}
+
+pub unsafe fn blsPublicKeyRecover(
+    pub_key: *mut BlsPublicKey,
+    pubVec: *const BlsPublicKey,
+    idVec: *const BlsId,
+    n: usize,
+) -> c_int {
+    if n == 0 {
+        return -1;
+    }
+    let keys = slice::from_raw_parts(pubVec, n);
+    let recover = || {
+        let coefficients = lagrange_coefficients_at_zero(&ids_of(slice::from_raw_parts(idVec, n)))?;
+        let mut result = B::g2_mul(&keys[0].v, &coefficients[0])?;
+        for (c, key) in coefficients.iter().zip(keys).skip(1) {
+            result = B::g2_add(&result, &B::g2_mul(&key.v, c)?)?;
+        }
+        Some(result)
+    };
+    match recover() {
+        Some(result) => {
+            (*pub_key).v = result;
+            0
+        }
+        None => -1,
+    }
+}
+
+pub unsafe fn blsSign(
+    sig: *mut BlsSignature,
+    sec: *const BlsSecretKey,
+    msg: *const c_void,
+    msgSize: usize,
+) {
+    if let Some(h) = B::g1_hash_and_map_to(bytes(msg, msgSize)) {
+        (*sig).v = checked(B::g1_mul(&h, &(*sec).v));
+    }
+}
+
+pub unsafe fn blsSignatureSerialize(
+    buf: *mut c_void,
+    maxBufSize: usize,
+    sig: *const BlsSignature,
+) -> usize {
+    mclBnG1_serialize(buf, maxBufSize, &(*sig).v)
+}
+
+pub unsafe fn blsSignatureDeserialize(
+    sig: *mut BlsSignature,
+    buf: *const c_void,
+    bufSize: usize,
+) -> usize {
+    mclBnG1_deserialize(&mut (*sig).v, buf, bufSize)
+}
+
+pub unsafe fn blsSignatureRecover(
+    sig: *mut BlsSignature,
+    sigVec: *const BlsSignature,
+    idVec: *const BlsId,
+    n: usize,
+) -> c_int {
+    if n == 0 {
+        return -1;
+    }
+    let signatures = slice::from_raw_parts(sigVec, n);
+    let recover = || {
+        let coefficients = lagrange_coefficients_at_zero(&ids_of(slice::from_raw_parts(idVec, n)))?;
+        let mut result = B::g1_mul(&signatures[0].v, &coefficients[0])?;
+        for (c, signature) in coefficients.iter().zip(signatures).skip(1) {
+            result = B::g1_add(&result, &B::g1_mul(&signature.v, c)?)?;
+        }
+        Some(result)
+    };
+    match recover() {
+        Some(result) => {
+            (*sig).v = result;
+            0
+        }
+        None => -1,
+    }
+}
+
+pub unsafe fn blsVerify(
+    sig: *const BlsSignature,
+    pub_key: *const BlsPublicKey,
+    msg: *const c_void,
+    msgSize: usize,
+) -> c_int {
+    let h = match B::g1_hash_and_map_to(bytes(msg, msgSize)) {
+        Some(h) => h,
+        None => return 0,
+    };
+    let verify = || {
+        let lhs = B::pairing(&(*sig).v, &B::g2_generator())?;
+        let rhs = B::pairing(&h, &(*pub_key).v)?;
+        B::gt_is_equal(&lhs, &rhs)
+    };
+    verify().unwrap_or(false) as c_int
+}
+
+pub unsafe fn blsIdDeserialize(id: *mut BlsId, buf: *const c_void, bufSize: usize) -> usize {
+    mclBnFr_deserialize(&mut (*id).v, buf, bufSize)
+}
@@ -0,0 +1,254 @@
+use super::CurveBackend;
+use crate::crypto::ffi::*;
+use std::mem;
+
+pub struct Mcl;
+
+fn serialize_with(size: usize, write: impl FnOnce(&mut [u8]) -> usize) -> Vec<u8> {
+    let mut buf = vec![0u8; size];
+    let written = write(&mut buf);
+    buf.truncate(written);
+    buf
+}
+
+impl CurveBackend for Mcl {
+    fn init() -> bool {
+        unsafe {
+            mclBn_init(MCL_BLS12_381, MCLBN_COMPILED_TIME_VAR) == 0
+                && blsInit(MCL_BLS12_381, MCLBN_COMPILED_TIME_VAR) == 0
+        }
+    }
+
+    fn fr_from_int(x: i64) -> mclBnFr {
+        unsafe {
+            let mut fr: mclBnFr = mem::zeroed();
+            mclBnFr_setInt(&mut fr, x);
+            fr
+        }
+    }
+
+    fn fr_random() -> Option<mclBnFr> {
+        unsafe {
+            let mut fr: mclBnFr = mem::zeroed();
+            (mclBnFr_setByCSPRNG(&mut fr) == 0).then_some(fr)
+        }
+    }
+
+    fn fr_hash_of(data: &[u8]) -> Option<mclBnFr> {
+        unsafe {
+            let mut fr: mclBnFr = mem::zeroed();
+            (mclBnFr_setHashOf(&mut fr, data.as_ptr() as *const _, data.len()) == 0).then_some(fr)
+        }
+    }
+
+    fn fr_serialize(x: &mclBnFr) -> Vec<u8> {
+        serialize_with(FR_SIZE, |buf| unsafe {
+            mclBnFr_serialize(buf.as_mut_ptr() as *mut _, buf.len(), x)
+        })
+    }
+
+    fn fr_deserialize(bytes: &[u8]) -> Option<mclBnFr> {
+        unsafe {
+            let mut fr: mclBnFr = mem::zeroed();
+            (mclBnFr_deserialize(&mut fr, bytes.as_ptr() as *const _, bytes.len()) != 0)
+                .then_some(fr)
+        }
+    }
+
+    fn fr_set_little_endian(bytes: &[u8]) -> Option<mclBnFr> {
+        unsafe {
+            let mut fr: mclBnFr = mem::zeroed();
+            (mclBnFr_setLittleEndian(&mut fr, bytes.as_ptr() as *const _, bytes.len()) == 0)
+                .then_some(fr)
+        }
+    }
+
+    fn fr_get_little_endian(x: &mclBnFr) -> Vec<u8> {
+        serialize_with(FR_SIZE, |buf| unsafe {
+            mclBnFr_getLittleEndian(buf.as_mut_ptr() as *mut _, buf.len(), x)
+        })
+    }
+
+    fn fr_is_zero(x: &mclBnFr) -> Option<bool> {
+        unsafe { Some(mclBnFr_isZero(x) != 0) }
+    }
+
+    fn fr_is_equal(x: &mclBnFr, y: &mclBnFr) -> Option<bool> {
+        unsafe { Some(mclBnFr_isEqual(x, y) != 0) }
+    }
+
+    fn fr_add(x: &mclBnFr, y: &mclBnFr) -> Option<mclBnFr> {
+        unsafe {
+            let mut z: mclBnFr = mem::zeroed();
+            mclBnFr_add(&mut z, x, y);
+            Some(z)
+        }
+    }
+
+    fn fr_sub(x: &mclBnFr, y: &mclBnFr) -> Option<mclBnFr> {
+        unsafe {
+            let mut z: mclBnFr = mem::zeroed();
+            mclBnFr_sub(&mut z, x, y);
+            Some(z)
+        }
+    }
+
+    fn fr_mul(x: &mclBnFr, y: &mclBnFr) -> Option<mclBnFr> {
+        unsafe {
+            let mut z: mclBnFr = mem::zeroed();
+            mclBnFr_mul(&mut z, x, y);
+            Some(z)
+        }
+    }
+
+    fn fr_div(x: &mclBnFr, y: &mclBnFr) -> Option<mclBnFr> {
+        unsafe {
+            let mut z: mclBnFr = mem::zeroed();
+            mclBnFr_div(&mut z, x, y);
+            Some(z)
+        }
+    }
+
+    fn g1_serialize(p: &mclBnG1) -> Vec<u8> {
+        serialize_with(G1_SIZE, |buf| unsafe {
+            mclBnG1_serialize(buf.as_mut_ptr() as *mut _, buf.len(), p)
+        })
+    }
+
+    fn g1_deserialize(bytes: &[u8]) -> Option<mclBnG1> {
+        unsafe {
+            let mut p: mclBnG1 = mem::zeroed();
+            (mclBnG1_deserialize(&mut p, bytes.as_ptr() as *const _, bytes.len()) != 0).then_some(p)
+        }
+    }
+
+    fn g1_hash_and_map_to(data: &[u8]) -> Option<mclBnG1> {
+        unsafe {
+            let mut p: mclBnG1 = mem::zeroed();
+            (mclBnG1_hashAndMapTo(&mut p, data.as_ptr() as *const _, data.len()) == 0).then_some(p)
+        }
+    }
+
+    fn fp_set_little_endian_mod(bytes: &[u8]) -> Option<mclBnFp> {
+        unsafe {
+            let mut x: mclBnFp = mem::zeroed();
+            (mclBnFp_setLittleEndianMod(&mut x, bytes.as_ptr() as *const _, bytes.len()) == 0)
+                .then_some(x)
+        }
+    }
+
+    fn g1_map_to(x: &mclBnFp) -> Option<mclBnG1> {
+        unsafe {
+            let mut p: mclBnG1 = mem::zeroed();
+            (mclBnFp_mapToG1(&mut p, x) == 0).then_some(p)
+        }
+    }
+
+    fn g1_mul(p: &mclBnG1, x: &mclBnFr) -> Option<mclBnG1> {
+        unsafe {
+            let mut q: mclBnG1 = mem::zeroed();
+            mclBnG1_mul(&mut q, p, x);
+            Some(q)
+        }
+    }
+
+    fn g1_add(p: &mclBnG1, q: &mclBnG1) -> Option<mclBnG1> {
+        unsafe {
+            let mut r: mclBnG1 = mem::zeroed();
+            mclBnG1_add(&mut r, p, q);
+            Some(r)
+        }
+    }
+
+    fn g1_is_equal(p: &mclBnG1, q: &mclBnG1) -> Option<bool> {
+        unsafe { Some(mclBnG1_isEqual(p, q) != 0) }
+    }
+
+    fn g1_normalize(p: &mclBnG1) -> Option<mclBnG1> {
+        unsafe {
+            let mut q: mclBnG1 = mem::zeroed();
+            mclBnG1_normalize(&mut q, p);
+            Some(q)
+        }
+    }
+
+    fn fp_serialize(x: &mclBnFp) -> Vec<u8> {
+        serialize_with(FP_SIZE, |buf| unsafe {
+            mclBnFp_serialize(buf.as_mut_ptr() as *mut _, buf.len(), x)
+        })
+    }
+
+    fn g2_generator() -> mclBnG2 {
+        unsafe {
+            let one = BlsSecretKey {
+                v: Self::fr_from_int(1),
+            };
+            let mut generator: BlsPublicKey = mem::zeroed();
+            blsGetPublicKey(&mut generator, &one);
+            generator.v
+        }
+    }
+
+    fn g2_serialize(p: &mclBnG2) -> Vec<u8> {
+        serialize_with(G2_SIZE, |buf| unsafe {
+            mclBnG2_serialize(buf.as_mut_ptr() as *mut _, buf.len(), p)
+        })
+    }
+
+    fn g2_deserialize(bytes: &[u8]) -> Option<mclBnG2> {
+        unsafe {
+            let mut p: mclBnG2 = mem::zeroed();
+            (mclBnG2_deserialize(&mut p, bytes.as_ptr() as *const _, bytes.len()) != 0).then_some(p)
+        }
+    }
+
+    fn g2_mul(p: &mclBnG2, x: &mclBnFr) -> Option<mclBnG2> {
+        unsafe {
+            let mut q: mclBnG2 = mem::zeroed();
+            mclBnG2_mul(&mut q, p, x);
+            Some(q)
+        }
+    }
+
+    fn g2_add(p: &mclBnG2, q: &mclBnG2) -> Option<mclBnG2> {
+        unsafe {
+            let mut r: mclBnG2 = mem::zeroed();
+            mclBnG2_add(&mut r, p, q);
+            Some(r)
+        }
+    }
+
+    fn g2_is_equal(p: &mclBnG2, q: &mclBnG2) -> Option<bool> {
+        unsafe { Some(mclBnG2_isEqual(p, q) != 0) }
+    }
+
+    fn g2_is_zero(p: &mclBnG2) -> Option<bool> {
+        unsafe { Some(mclBnG2_isZero(p) != 0) }
+    }
+
+    fn gt_serialize(x: &mclBnGT) -> Vec<u8> {
+        serialize_with(GT_SIZE, |buf| unsafe {
+            mclBnGT_serialize(buf.as_mut_ptr() as *mut _, buf.len(), x)
+        })
+    }
+
+    fn gt_mul(x: &mclBnGT, y: &mclBnGT) -> Option<mclBnGT> {
+        unsafe {
+            let mut z: mclBnGT = mem::zeroed();
+            mclBnGT_mul(&mut z, x, y);
+            Some(z)
+        }
+    }
+
+    fn gt_is_equal(x: &mclBnGT, y: &mclBnGT) -> Option<bool> {
+        unsafe { Some(mclBnGT_isEqual(x, y) != 0) }
+    }
+
+    fn pairing(p: &mclBnG1, q: &mclBnG2) -> Option<mclBnGT> {
+        unsafe {
+            let mut e: mclBnGT = mem::zeroed();
+            mclBn_pairing(&mut e, p, q);
+            Some(e)
+        }
+    }
+}
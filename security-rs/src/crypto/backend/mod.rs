@@ -0,0 +1,61 @@
+use super::ffi::{mclBnFp, mclBnFr, mclBnG1, mclBnG2, mclBnGT};
+
+#[cfg(all(feature = "pure-rust", not(feature = "mcl")))]
+pub(crate) mod abi;
+#[cfg(feature = "mcl")]
+mod mcl;
+#[cfg(feature = "pure-rust")]
+mod pure_rust;
+
+#[cfg(feature = "mcl")]
+pub use mcl::Mcl;
+#[cfg(feature = "pure-rust")]
+pub use pure_rust::PureRust;
+
+#[cfg(not(any(feature = "mcl", feature = "pure-rust")))]
+compile_error!("enable either the `mcl` or the `pure-rust` curve backend feature");
+
+/// Operations on elements the backend did not produce itself return `None`
+/// (or an empty buffer when serializing) if the element is malformed.
+pub trait CurveBackend {
+    fn init() -> bool;
+
+    fn fr_from_int(x: i64) -> mclBnFr;
+    fn fr_random() -> Option<mclBnFr>;
+    fn fr_hash_of(data: &[u8]) -> Option<mclBnFr>;
+    fn fr_serialize(x: &mclBnFr) -> Vec<u8>;
+    fn fr_deserialize(bytes: &[u8]) -> Option<mclBnFr>;
+    fn fr_set_little_endian(bytes: &[u8]) -> Option<mclBnFr>;
+    fn fr_get_little_endian(x: &mclBnFr) -> Vec<u8>;
+    fn fr_is_zero(x: &mclBnFr) -> Option<bool>;
+    fn fr_is_equal(x: &mclBnFr, y: &mclBnFr) -> Option<bool>;
+    fn fr_add(x: &mclBnFr, y: &mclBnFr) -> Option<mclBnFr>;
+    fn fr_sub(x: &mclBnFr, y: &mclBnFr) -> Option<mclBnFr>;
+    fn fr_mul(x: &mclBnFr, y: &mclBnFr) -> Option<mclBnFr>;
+    fn fr_div(x: &mclBnFr, y: &mclBnFr) -> Option<mclBnFr>;
+
+    fn g1_serialize(p: &mclBnG1) -> Vec<u8>;
+    fn g1_deserialize(bytes: &[u8]) -> Option<mclBnG1>;
+    fn g1_hash_and_map_to(data: &[u8]) -> Option<mclBnG1>;
+    fn fp_set_little_endian_mod(bytes: &[u8]) -> Option<mclBnFp>;
+    fn g1_map_to(x: &mclBnFp) -> Option<mclBnG1>;
+    fn g1_mul(p: &mclBnG1, x: &mclBnFr) -> Option<mclBnG1>;
+    fn g1_add(p: &mclBnG1, q: &mclBnG1) -> Option<mclBnG1>;
+    fn g1_is_equal(p: &mclBnG1, q: &mclBnG1) -> Option<bool>;
+    fn g1_normalize(p: &mclBnG1) -> Option<mclBnG1>;
+    fn fp_serialize(x: &mclBnFp) -> Vec<u8>;
+
+    fn g2_generator() -> mclBnG2;
+    fn g2_serialize(p: &mclBnG2) -> Vec<u8>;
+    fn g2_deserialize(bytes: &[u8]) -> Option<mclBnG2>;
+    fn g2_mul(p: &mclBnG2, x: &mclBnFr) -> Option<mclBnG2>;
+    fn g2_add(p: &mclBnG2, q: &mclBnG2) -> Option<mclBnG2>;
+    fn g2_is_equal(p: &mclBnG2, q: &mclBnG2) -> Option<bool>;
+    fn g2_is_zero(p: &mclBnG2) -> Option<bool>;
+
+    fn gt_serialize(x: &mclBnGT) -> Vec<u8>;
+    fn gt_mul(x: &mclBnGT, y: &mclBnGT) -> Option<mclBnGT>;
+    fn gt_is_equal(x: &mclBnGT, y: &mclBnGT) -> Option<bool>;
+
+    fn pairing(p: &mclBnG1, q: &mclBnG2) -> Option<mclBnGT>;
+}
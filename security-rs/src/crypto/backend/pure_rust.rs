@@ -0,0 +1,483 @@
+use super::CurveBackend;
+use crate::crypto::ffi::{mclBnFp, mclBnFp2, mclBnFr, mclBnG1, mclBnG2, mclBnGT};
+use ark_bls12_381::{g1, g2, Bls12_381, Fq, Fq12, Fq2, Fq6, Fr, G1Affine, G2Affine};
+use ark_ec::pairing::Pairing;
+use ark_ec::short_weierstrass::SWCurveConfig;
+use ark_ec::{AffineRepr, CurveConfig, CurveGroup};
+use ark_ff::{BigInt, BigInteger, Field, LegendreSymbol, One, PrimeField, UniformRand, Zero};
+use rand::rngs::OsRng;
+use sha2::{Digest, Sha256, Sha512};
+use std::sync::OnceLock;
+
+pub struct PureRust;
+
+const FR_BYTES: usize = 32;
+const FP_BYTES: usize = 48;
+
+fn limbs_from_le<const N: usize>(bytes: &[u8]) -> [u64; N] {
+    let mut limbs = [0u64; N];
+    for (i, chunk) in bytes.chunks(8).take(N).enumerate() {
+        let mut word = [0u8; 8];
+        word[..chunk.len()].copy_from_slice(chunk);
+        limbs[i] = u64::from_le_bytes(word);
+    }
+    limbs
+}
+
+fn fr_in(x: &mclBnFr) -> Option<Fr> {
+    Fr::from_bigint(BigInt::new(x.d))
+}
+
+fn fr_out(x: Fr) -> mclBnFr {
+    mclBnFr {
+        d: x.into_bigint().0,
+    }
+}
+
+fn fp_in(x: &mclBnFp) -> Option<Fq> {
+    Fq::from_bigint(BigInt::new(x.d))
+}
+
+fn fp_out(x: &Fq) -> mclBnFp {
+    mclBnFp {
+        d: x.into_bigint().0,
+    }
+}
+
+fn fp2_in(x: &mclBnFp2) -> Option<Fq2> {
+    Some(Fq2::new(fp_in(&x.d[0])?, fp_in(&x.d[1])?))
+}
+
+fn fp2_out(x: &Fq2) -> mclBnFp2 {
+    mclBnFp2 {
+        d: [fp_out(&x.c0), fp_out(&x.c1)],
+    }
+}
+
+fn fp_to_le(x: &Fq) -> Vec<u8> {
+    x.into_bigint().to_bytes_le()
+}
+
+fn fp_from_le(bytes: &[u8]) -> Option<Fq> {
+    Fq::from_bigint(BigInt::new(limbs_from_le(bytes)))
+}
+
+fn fp_is_odd(x: &Fq) -> bool {
+    x.into_bigint().is_odd()
+}
+
+fn g1_in(p: &mclBnG1) -> Option<G1Affine> {
+    if p.z.d.iter().all(|limb| *limb == 0) {
+        Some(G1Affine::identity())
+    } else {
+        Some(G1Affine::new_unchecked(fp_in(&p.x)?, fp_in(&p.y)?))
+    }
+}
+
+fn g1_out(p: &G1Affine) -> mclBnG1 {
+    match p.xy() {
+        Some((x, y)) => mclBnG1 {
+            x: fp_out(x),
+            y: fp_out(y),
+            z: fp_out(&Fq::one()),
+        },
+        None => mclBnG1 {
+            x: mclBnFp { d: [0; 6] },
+            y: mclBnFp { d: [0; 6] },
+            z: mclBnFp { d: [0; 6] },
+        },
+    }
+}
+
+fn g2_in(p: &mclBnG2) -> Option<G2Affine> {
+    if p.z.d.iter().all(|fp| fp.d.iter().all(|limb| *limb == 0)) {
+        Some(G2Affine::identity())
+    } else {
+        Some(G2Affine::new_unchecked(fp2_in(&p.x)?, fp2_in(&p.y)?))
+    }
+}
+
+fn g2_out(p: &G2Affine) -> mclBnG2 {
+    let zero = mclBnFp2 {
+        d: [mclBnFp { d: [0; 6] }, mclBnFp { d: [0; 6] }],
+    };
+    match p.xy() {
+        Some((x, y)) => mclBnG2 {
+            x: fp2_out(x),
+            y: fp2_out(y),
+            z: fp2_out(&Fq2::one()),
+        },
+        None => mclBnG2 {
+            x: zero,
+            y: zero,
+            z: zero,
+        },
+    }
+}
+
+fn fq12_coefficients(x: &Fq12) -> [Fq; 12] {
+    let mut coefficients = [Fq::zero(); 12];
+    for (i, fp6) in [x.c0, x.c1].iter().enumerate() {
+        for (j, fp2) in [fp6.c0, fp6.c1, fp6.c2].iter().enumerate() {
+            coefficients[i * 6 + j * 2] = fp2.c0;
+            coefficients[i * 6 + j * 2 + 1] = fp2.c1;
+        }
+    }
+    coefficients
+}
+
+fn gt_in(x: &mclBnGT) -> Option<Fq12> {
+    let c: Vec<Fq> = x.d.iter().map(fp_in).collect::<Option<_>>()?;
+    Some(Fq12::new(
+        Fq6::new(
+            Fq2::new(c[0], c[1]),
+            Fq2::new(c[2], c[3]),
+            Fq2::new(c[4], c[5]),
+        ),
+        Fq6::new(
+            Fq2::new(c[6], c[7]),
+            Fq2::new(c[8], c[9]),
+            Fq2::new(c[10], c[11]),
+        ),
+    ))
+}
+
+fn gt_out(x: &Fq12) -> mclBnGT {
+    let coefficients = fq12_coefficients(x);
+    let mut gt = mclBnGT {
+        d: [mclBnFp { d: [0; 6] }; 12],
+    };
+    for (out, c) in gt.d.iter_mut().zip(coefficients.iter()) {
+        *out = fp_out(c);
+    }
+    gt
+}
+
+// mcl takes square roots in Fp as x^((p + 1) / 4) and in Fp2 with the complex method;
+// the map-to-curve below depends on picking the same root.
+fn fp_sqrt(x: &Fq) -> Option<Fq> {
+    let mut exponent = Fq::MODULUS;
+    exponent.add_with_carry(&BigInt::from(1u64));
+    exponent.div2();
+    exponent.div2();
+    let root = x.pow(exponent);
+    (root.square() == *x).then_some(root)
+}
+
+fn fp2_sqrt(x: &Fq2) -> Option<Fq2> {
+    let two_inv = Fq::from(2u64).inverse()?;
+    if x.c1.is_zero() {
+        return match fp_sqrt(&x.c0) {
+            Some(root) => Some(Fq2::new(root, Fq::zero())),
+            None => fp_sqrt(&-x.c0).map(|root| Fq2::new(Fq::zero(), root)),
+        };
+    }
+
+    let norm_root = fp_sqrt(&(x.c0.square() + x.c1.square()))?;
+    let a = match fp_sqrt(&((x.c0 + norm_root) * two_inv)) {
+        Some(a) => a,
+        None => fp_sqrt(&((x.c0 - norm_root) * two_inv))?,
+    };
+    let b = x.c1 * (a + a).inverse()?;
+    Some(Fq2::new(a, b))
+}
+
+trait MapField: Field {
+    fn from_fp(x: Fq) -> Self;
+    fn mcl_sqrt(&self) -> Option<Self>;
+    fn is_negative(&self) -> bool;
+}
+
+impl MapField for Fq {
+    fn from_fp(x: Fq) -> Self {
+        x
+    }
+
+    fn mcl_sqrt(&self) -> Option<Self> {
+        fp_sqrt(self)
+    }
+
+    fn is_negative(&self) -> bool {
+        self.legendre() == LegendreSymbol::QuadraticNonResidue
+    }
+}
+
+impl MapField for Fq2 {
+    fn from_fp(x: Fq) -> Self {
+        Fq2::new(x, Fq::zero())
+    }
+
+    fn mcl_sqrt(&self) -> Option<Self> {
+        fp2_sqrt(self)
+    }
+
+    fn is_negative(&self) -> bool {
+        self.norm().legendre() == LegendreSymbol::QuadraticNonResidue
+    }
+}
+
+// Fouque-Tibouchi map used by mcl's original (non hash-to-curve) mapTo mode.
+fn map_to_curve<F: MapField>(t: F, b: F) -> Option<(F, F)> {
+    if t.is_zero() {
+        return None;
+    }
+
+    let c1 = fp_sqrt(&-Fq::from(3u64))?;
+    let c2 = (c1 - Fq::one()) * Fq::from(2u64).inverse()?;
+    let negative = t.is_negative();
+
+    let w = (t.square() + b + F::one()).inverse()? * F::from_fp(c1) * t;
+    let mut x = F::zero();
+    for i in 0..3 {
+        x = match i {
+            0 => F::from_fp(c2) - t * w,
+            1 => -x - F::one(),
+            _ => w.square().inverse()? + F::one(),
+        };
+        if let Some(y) = (x.square() * x + b).mcl_sqrt() {
+            return Some((x, if negative { -y } else { y }));
+        }
+    }
+    None
+}
+
+fn hash_to_fp(data: &[u8]) -> Fq {
+    let digest = Sha512::digest(data);
+    let mut bytes = [0u8; FP_BYTES];
+    bytes.copy_from_slice(&digest[..FP_BYTES]);
+    bytes[FP_BYTES - 1] &= 0x0f;
+    fp_from_le(&bytes).unwrap_or_default()
+}
+
+fn g2_generator_affine() -> G2Affine {
+    static GENERATOR: OnceLock<G2Affine> = OnceLock::new();
+    *GENERATOR.get_or_init(|| {
+        let (x, y) = map_to_curve(Fq2::one(), g2::Config::COEFF_B)
+            .expect("mapToG2(1) is defined on BLS12-381");
+        G2Affine::new_unchecked(x, y).clear_cofactor()
+    })
+}
+
+impl CurveBackend for PureRust {
+    fn init() -> bool {
+        g2_generator_affine().is_on_curve()
+    }
+
+    fn fr_from_int(x: i64) -> mclBnFr {
+        let magnitude = Fr::from(x.unsigned_abs());
+        fr_out(if x < 0 { -magnitude } else { magnitude })
+    }
+
+    fn fr_random() -> Option<mclBnFr> {
+        Some(fr_out(Fr::rand(&mut OsRng)))
+    }
+
+    fn fr_hash_of(data: &[u8]) -> Option<mclBnFr> {
+        let mut bytes: [u8; FR_BYTES] = Sha256::digest(data).into();
+        bytes[FR_BYTES - 1] &= 0x3f;
+        Self::fr_deserialize(&bytes)
+    }
+
+    fn fr_serialize(x: &mclBnFr) -> Vec<u8> {
+        fr_in(x).map_or_else(Vec::new, |x| x.into_bigint().to_bytes_le())
+    }
+
+    fn fr_deserialize(bytes: &[u8]) -> Option<mclBnFr> {
+        if bytes.len() < FR_BYTES {
+            return None;
+        }
+        Fr::from_bigint(BigInt::new(limbs_from_le(&bytes[..FR_BYTES]))).map(fr_out)
+    }
+
+    fn fr_set_little_endian(bytes: &[u8]) -> Option<mclBnFr> {
+        if bytes.len() > FR_BYTES {
+            return None;
+        }
+        let mut buf = [0u8; FR_BYTES];
+        buf[..bytes.len()].copy_from_slice(bytes);
+        buf[FR_BYTES - 1] &= 0x3f;
+        Self::fr_deserialize(&buf)
+    }
+
+    fn fr_get_little_endian(x: &mclBnFr) -> Vec<u8> {
+        Self::fr_serialize(x)
+    }
+
+    fn fr_is_zero(x: &mclBnFr) -> Option<bool> {
+        Some(fr_in(x)?.is_zero())
+    }
+
+    fn fr_is_equal(x: &mclBnFr, y: &mclBnFr) -> Option<bool> {
+        Some(fr_in(x)? == fr_in(y)?)
+    }
+
+    fn fr_add(x: &mclBnFr, y: &mclBnFr) -> Option<mclBnFr> {
+        Some(fr_out(fr_in(x)? + fr_in(y)?))
+    }
+
+    fn fr_sub(x: &mclBnFr, y: &mclBnFr) -> Option<mclBnFr> {
+        Some(fr_out(fr_in(x)? - fr_in(y)?))
+    }
+
+    fn fr_mul(x: &mclBnFr, y: &mclBnFr) -> Option<mclBnFr> {
+        Some(fr_out(fr_in(x)? * fr_in(y)?))
+    }
+
+    fn fr_div(x: &mclBnFr, y: &mclBnFr) -> Option<mclBnFr> {
+        // mcl divides by zero to zero.
+        let inverse = fr_in(y)?.inverse().unwrap_or_default();
+        Some(fr_out(fr_in(x)? * inverse))
+    }
+
+    fn g1_serialize(p: &mclBnG1) -> Vec<u8> {
+        let Some(p) = g1_in(p) else {
+            return Vec::new();
+        };
+        match p.xy() {
+            Some((x, y)) => {
+                let mut bytes = fp_to_le(x);
+                if fp_is_odd(y) {
+                    bytes[FP_BYTES - 1] |= 0x80;
+                }
+                bytes
+            }
+            None => vec![0u8; FP_BYTES],
+        }
+    }
+
+    fn g1_deserialize(bytes: &[u8]) -> Option<mclBnG1> {
+        if bytes.len() < FP_BYTES {
+            return None;
+        }
+        let mut buf = bytes[..FP_BYTES].to_vec();
+        if buf.iter().all(|b| *b == 0) {
+            return Some(g1_out(&G1Affine::identity()));
+        }
+        let odd = buf[FP_BYTES - 1] & 0x80 != 0;
+        buf[FP_BYTES - 1] &= 0x7f;
+
+        let x = fp_from_le(&buf)?;
+        let mut y = fp_sqrt(&(x.square() * x + g1::Config::COEFF_B))?;
+        if fp_is_odd(&y) != odd {
+            y = -y;
+        }
+        let point = G1Affine::new_unchecked(x, y);
+        point
+            .is_in_correct_subgroup_assuming_on_curve()
+            .then(|| g1_out(&point))
+    }
+
+    fn g1_hash_and_map_to(data: &[u8]) -> Option<mclBnG1> {
+        Self::g1_map_to(&fp_out(&hash_to_fp(data)))
+    }
+
+    fn fp_set_little_endian_mod(bytes: &[u8]) -> Option<mclBnFp> {
+        if bytes.len() > 2 * FP_BYTES {
+            return None;
+        }
+        Some(fp_out(&Fq::from_le_bytes_mod_order(bytes)))
+    }
+
+    fn g1_map_to(x: &mclBnFp) -> Option<mclBnG1> {
+        let (x, y) = map_to_curve(fp_in(x)?, g1::Config::COEFF_B)?;
+        let point = g1::Config::mul_affine(&G1Affine::new_unchecked(x, y), g1::Config::COFACTOR);
+        Some(g1_out(&point.into_affine()))
+    }
+
+    fn g1_mul(p: &mclBnG1, x: &mclBnFr) -> Option<mclBnG1> {
+        Some(g1_out(&(g1_in(p)? * fr_in(x)?).into_affine()))
+    }
+
+    fn g1_add(p: &mclBnG1, q: &mclBnG1) -> Option<mclBnG1> {
+        Some(g1_out(&(g1_in(p)? + g1_in(q)?).into_affine()))
+    }
+
+    fn g1_is_equal(p: &mclBnG1, q: &mclBnG1) -> Option<bool> {
+        Some(g1_in(p)? == g1_in(q)?)
+    }
+
+    fn g1_normalize(p: &mclBnG1) -> Option<mclBnG1> {
+        Some(g1_out(&g1_in(p)?))
+    }
+
+    fn fp_serialize(x: &mclBnFp) -> Vec<u8> {
+        fp_in(x).map(|x| fp_to_le(&x)).unwrap_or_default()
+    }
+
+    fn g2_generator() -> mclBnG2 {
+        g2_out(&g2_generator_affine())
+    }
+
+    fn g2_serialize(p: &mclBnG2) -> Vec<u8> {
+        let Some(p) = g2_in(p) else {
+            return Vec::new();
+        };
+        match p.xy() {
+            Some((x, y)) => {
+                let mut bytes = fp_to_le(&x.c0);
+                bytes.extend_from_slice(&fp_to_le(&x.c1));
+                if fp_is_odd(&y.c0) {
+                    bytes[2 * FP_BYTES - 1] |= 0x80;
+                }
+                bytes
+            }
+            None => vec![0u8; 2 * FP_BYTES],
+        }
+    }
+
+    fn g2_deserialize(bytes: &[u8]) -> Option<mclBnG2> {
+        if bytes.len() < 2 * FP_BYTES {
+            return None;
+        }
+        let mut buf = bytes[..2 * FP_BYTES].to_vec();
+        if buf.iter().all(|b| *b == 0) {
+            return Some(g2_out(&G2Affine::identity()));
+        }
+        let odd = buf[2 * FP_BYTES - 1] & 0x80 != 0;
+        buf[2 * FP_BYTES - 1] &= 0x7f;
+
+        let x = Fq2::new(fp_from_le(&buf[..FP_BYTES])?, fp_from_le(&buf[FP_BYTES..])?);
+        let mut y = (x.square() * x + g2::Config::COEFF_B).sqrt()?;
+        if fp_is_odd(&y.c0) != odd {
+            y = -y;
+        }
+        let point = G2Affine::new_unchecked(x, y);
+        point
+            .is_in_correct_subgroup_assuming_on_curve()
+            .then(|| g2_out(&point))
+    }
+
+    fn g2_mul(p: &mclBnG2, x: &mclBnFr) -> Option<mclBnG2> {
+        Some(g2_out(&(g2_in(p)? * fr_in(x)?).into_affine()))
+    }
+
+    fn g2_add(p: &mclBnG2, q: &mclBnG2) -> Option<mclBnG2> {
+        Some(g2_out(&(g2_in(p)? + g2_in(q)?).into_affine()))
+    }
+
+    fn g2_is_equal(p: &mclBnG2, q: &mclBnG2) -> Option<bool> {
+        Some(g2_in(p)? == g2_in(q)?)
+    }
+
+    fn g2_is_zero(p: &mclBnG2) -> Option<bool> {
+        Some(g2_in(p)?.is_zero())
+    }
+
+    fn gt_serialize(x: &mclBnGT) -> Vec<u8> {
+        gt_in(x).map_or_else(Vec::new, |x| {
+            fq12_coefficients(&x).iter().flat_map(fp_to_le).collect()
+        })
+    }
+
+    fn gt_mul(x: &mclBnGT, y: &mclBnGT) -> Option<mclBnGT> {
+        Some(gt_out(&(gt_in(x)? * gt_in(y)?)))
+    }
+
+    fn gt_is_equal(x: &mclBnGT, y: &mclBnGT) -> Option<bool> {
+        Some(gt_in(x)? == gt_in(y)?)
+    }
+
+    fn pairing(p: &mclBnG1, q: &mclBnG2) -> Option<mclBnGT> {
+        Some(gt_out(&Bls12_381::pairing(g1_in(p)?, g2_in(q)?).0))
+    }
+}
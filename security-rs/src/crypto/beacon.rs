@@ -0,0 +1,134 @@
+use super::threshold::{combine_partial_signatures, verify_partial};
+use super::utils::{bytes_to_hex, hex_to_bytes};
+use super::verify;
+use crate::types::{ActorShare, BeaconRound, PartialSignature, PublicShare};
+use sha2::{Digest, Sha256};
+
+const BEACON_DST: &[u8] = b"SECURITY-RS-BEACON-V1";
+
+fn check_chain(actor_id: &str, round: u64, previous: Option<&BeaconRound>) -> Result<(), String> {
+    match previous {
+        None if round != 0 => Err(format!("Round {} requires the previous round", round)),
+        None => Ok(()),
+        Some(previous) if previous.actor_id != actor_id => {
+            Err("Previous round belongs to a different actor".to_string())
+        }
+        Some(previous) if previous.round.checked_add(1) != Some(round) => Err(format!(
+            "Round {} does not follow previous round {}",
+            round, previous.round
+        )),
+        Some(_) => Ok(()),
+    }
+}
+
+fn previous_signature(previous: Option<&BeaconRound>) -> String {
+    previous
+        .map(|previous| previous.signature.clone())
+        .unwrap_or_default()
+}
+
+pub fn beacon_message(
+    actor_id: &str,
+    round: u64,
+    previous_signature: &str,
+) -> Result<Vec<u8>, String> {
+    let mut message = BEACON_DST.to_vec();
+    message.extend_from_slice(&(actor_id.len() as u32).to_be_bytes());
+    message.extend_from_slice(actor_id.as_bytes());
+    message.extend_from_slice(&round.to_be_bytes());
+    message.extend_from_slice(&hex_to_bytes(previous_signature)?);
+    Ok(message)
+}
+
+pub fn beacon_randomness(signature_hex: &str) -> Result<String, String> {
+    let signature = hex_to_bytes(signature_hex)?;
+    Ok(bytes_to_hex(&Sha256::digest(&signature)))
+}
+
+pub fn generate_beacon_partial(
+    actor_share: &ActorShare,
+    round: u64,
+    previous: Option<&BeaconRound>,
+) -> Result<PartialSignature, String> {
+    check_chain(&actor_share.actor_id, round, previous)?;
+    let message = beacon_message(&actor_share.actor_id, round, &previous_signature(previous))?;
+    actor_share.partial_sign(&message)
+}
+
+pub fn combine_beacon_partials(
+    actor_id: &str,
+    round: u64,
+    previous: Option<&BeaconRound>,
+    group_key_hex: &str,
+    threshold: usize,
+    partials: &[PartialSignature],
+    public_shares: &[PublicShare],
+) -> Result<BeaconRound, String> {
+    if threshold == 0 {
+        return Err("Threshold must be at least 1".to_string());
+    }
+    check_chain(actor_id, round, previous)?;
+
+    let previous_signature = previous_signature(previous);
+    let message = beacon_message(actor_id, round, &previous_signature)?;
+
+    let mut valid: Vec<PartialSignature> = Vec::new();
+    for partial in partials {
+        if valid.len() == threshold {
+            break;
+        }
+        if valid.iter().any(|v| v.id == partial.id) {
+            continue;
+        }
+        let public_share = match public_shares.iter().find(|ph| ph.id == partial.id) {
+            Some(public_share) => public_share,
+            None => continue,
+        };
+        if verify_partial(partial, public_share, &message).is_ok() {
+            valid.push(partial.clone());
+        }
+    }
+
+    if valid.len() < threshold {
+        return Err(format!(
+            "Not enough valid beacon partials: got {}, need {}",
+            valid.len(),
+            threshold
+        ));
+    }
+
+    let signature = combine_partial_signatures(threshold, &valid)?;
+    if !verify(&message, &signature, group_key_hex)? {
+        return Err("INVALID_BEACON_SIGNATURE".to_string());
+    }
+
+    Ok(BeaconRound {
+        actor_id: actor_id.to_string(),
+        round,
+        previous_signature,
+        randomness: beacon_randomness(&signature)?,
+        signature,
+    })
+}
+
+pub fn verify_beacon_round(
+    beacon: &BeaconRound,
+    group_key_hex: &str,
+    previous: Option<&BeaconRound>,
+) -> Result<(), String> {
+    check_chain(&beacon.actor_id, beacon.round, previous)?;
+    if beacon.previous_signature != previous_signature(previous) {
+        return Err("Beacon does not chain on the previous signature".to_string());
+    }
+
+    let message = beacon_message(&beacon.actor_id, beacon.round, &beacon.previous_signature)?;
+    if !verify(&message, &beacon.signature, group_key_hex)? {
+        return Err("INVALID_BEACON_SIGNATURE".to_string());
+    }
+
+    if beacon.randomness != beacon_randomness(&beacon.signature)? {
+        return Err("Beacon randomness does not match signature".to_string());
+    }
+
+    Ok(())
+}
@@ -171,6 +171,60 @@ pub fn get_g2_generator() -> mclBnG2 {
     }
 }
 
+// The standard BLS12-381 G1 generator, in mcl's serialization
+const G1_GENERATOR: &str = "bbc622db0af03afbef1a7af93fe8556c58ac1b173f3a4ea105b974974f8c68c30faca94f8c63952694d79731a7d3f197";
+// (p - 1) / 2 big-endian; a compressed point flags y above it as the larger root
+const FP_HALF_MODULUS: &str = "0d0088f51cbff34d258dd3db21a5d66bb23ba5c279c2895fb39869507b587b120f55ffff58a9ffffdcff7fffffffd555";
+
+pub fn get_g1_generator() -> mclBnG1 {
+    let bytes = hex_to_bytes(G1_GENERATOR).expect("Invalid G1 generator hex");
+    deserialize_g1(&bytes).expect("Invalid G1 generator")
+}
+
+/// sk·G1 against the standard generator, as Ethereum derives BLS public keys.
+pub fn derive_public_key_g1(secret_key_fr: &mclBnFr) -> mclBnG1 {
+    unsafe {
+        let mut pub_key: mclBnG1 = mem::zeroed();
+        mclBnG1_mul(&mut pub_key, &get_g1_generator(), secret_key_fr);
+        pub_key
+    }
+}
+
+/// Serializes `g1` in the compressed big-endian (ZCash) encoding used by
+/// Ethereum, rather than mcl's little-endian one.
+pub fn serialize_g1_compressed(g1: &mclBnG1) -> Result<Vec<u8>, String> {
+    let mcl_bytes = serialize_g1(g1);
+    if mcl_bytes.len() != G1_SIZE {
+        return Err("Failed to serialize G1".to_string());
+    }
+    if mcl_bytes.iter().all(|b| *b == 0) {
+        let mut bytes = vec![0u8; G1_SIZE];
+        bytes[0] = 0xc0;
+        return Ok(bytes);
+    }
+
+    unsafe {
+        let mut affine: mclBnG1 = mem::zeroed();
+        mclBnG1_normalize(&mut affine, g1);
+
+        let mut x = vec![0u8; FP_SIZE];
+        let mut y = vec![0u8; FP_SIZE];
+        if mclBnFp_serialize(x.as_mut_ptr() as *mut _, FP_SIZE, &affine.x) != FP_SIZE
+            || mclBnFp_serialize(y.as_mut_ptr() as *mut _, FP_SIZE, &affine.y) != FP_SIZE
+        {
+            return Err("Failed to serialize G1 coordinates".to_string());
+        }
+        x.reverse();
+        y.reverse();
+
+        x[0] |= 0x80;
+        if y > hex_to_bytes(FP_HALF_MODULUS)? {
+            x[0] |= 0x20;
+        }
+        Ok(x)
+    }
+}
+
 pub fn hash_to_g1(data: &[u8]) -> Result<mclBnG1, String> {
     unsafe {
         let mut g1: mclBnG1 = mem::zeroed();
@@ -183,6 +237,22 @@ pub fn hash_to_g1(data: &[u8]) -> Result<mclBnG1, String> {
     }
 }
 
+/// Maps `digest`, reduced mod p, onto G1 without the hashing step of
+/// `hash_to_g1`.
+pub fn map_to_g1(digest: &[u8]) -> Result<mclBnG1, String> {
+    unsafe {
+        let mut fp: mclBnFp = mem::zeroed();
+        if mclBnFp_setLittleEndianMod(&mut fp, digest.as_ptr() as *const _, digest.len()) != 0 {
+            return Err("Failed to reduce digest to Fp".to_string());
+        }
+        let mut g1: mclBnG1 = mem::zeroed();
+        if mclBnFp_mapToG1(&mut g1, &fp) != 0 {
+            return Err("Failed to map to G1".to_string());
+        }
+        Ok(g1)
+    }
+}
+
 pub fn hash_to_fr(data: &[u8]) -> Result<mclBnFr, String> {
     unsafe {
         let mut fr: mclBnFr = mem::zeroed();
@@ -268,3 +338,29 @@ pub fn sign_direct(data: &[u8], secret_key_hex: &str) -> Result<String, String>
         Ok(bytes_to_hex(&sig_buf[..sig_size]))
     }
 }
+
+pub fn verify(data: &[u8], signature_hex: &str, public_key_hex: &str) -> Result<bool, String> {
+    unsafe {
+        let sig_bytes = hex_to_bytes(signature_hex)?;
+        let mut sig: BlsSignature = mem::zeroed();
+        let consumed =
+            blsSignatureDeserialize(&mut sig, sig_bytes.as_ptr() as *const _, sig_bytes.len());
+        if consumed == 0 {
+            return Err("Failed to deserialize signature".to_string());
+        }
+
+        let pk_bytes = hex_to_bytes(public_key_hex)?;
+        let mut pub_key: BlsPublicKey = mem::zeroed();
+        let consumed =
+            blsPublicKeyDeserialize(&mut pub_key, pk_bytes.as_ptr() as *const _, pk_bytes.len());
+        if consumed == 0 {
+            return Err("Failed to deserialize public key".to_string());
+        }
+
+        let mut hasher = Sha512::new();
+        hasher.update(data);
+        let hash = hasher.finalize();
+
+        Ok(blsVerify(&sig, &pub_key, hash.as_ptr() as *const _, hash.len()) == 1)
+    }
+}
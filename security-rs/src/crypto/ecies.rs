@@ -0,0 +1,124 @@
+use super::bls::{derive_public_key_g2, deserialize_fr, deserialize_g2, serialize_g2};
+use super::ffi::*;
+use super::utils::{bytes_to_hex, hex_to_bytes};
+use crate::types::EncryptedData;
+use aes::cipher::{block_padding::Pkcs7, BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use hmac::{Hmac, Mac};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::{Digest, Sha512};
+use std::mem;
+
+type Aes256CbcEnc = cbc::Encryptor<aes::Aes256>;
+type Aes256CbcDec = cbc::Decryptor<aes::Aes256>;
+type HmacSha512 = Hmac<Sha512>;
+
+const KEY_LENGTH_IN_BYTE: usize = 32;
+const IV_LENGTH_IN_BYTE: usize = 16;
+
+fn derive_ecies_keys(shared_secret: &[u8]) -> (Vec<u8>, Vec<u8>) {
+    let hash = Sha512::digest(shared_secret);
+    (
+        hash[..KEY_LENGTH_IN_BYTE].to_vec(),
+        hash[KEY_LENGTH_IN_BYTE..].to_vec(),
+    )
+}
+
+fn ecies_mac(
+    mac_key: &[u8],
+    iv: &[u8],
+    ephemeral_public_key: &[u8],
+    cipher_data: &[u8],
+) -> HmacSha512 {
+    let mut mac = HmacSha512::new_from_slice(mac_key).expect("HMAC accepts any key length");
+    mac.update(iv);
+    mac.update(ephemeral_public_key);
+    mac.update(cipher_data);
+    mac
+}
+
+pub(crate) fn seal(
+    data: &[u8],
+    shared_secret: &[u8],
+    ephemeral_public_key: &[u8],
+) -> Result<EncryptedData, String> {
+    let (encryption_key, mac_key) = derive_ecies_keys(shared_secret);
+
+    let mut iv = [0u8; IV_LENGTH_IN_BYTE];
+    OsRng.fill_bytes(&mut iv);
+
+    let cipher_data = Aes256CbcEnc::new_from_slices(&encryption_key, &iv)
+        .map_err(|e| format!("Failed to initialize AES: {}", e))?
+        .encrypt_padded_vec_mut::<Pkcs7>(data);
+    let mac = ecies_mac(&mac_key, &iv, ephemeral_public_key, &cipher_data)
+        .finalize()
+        .into_bytes();
+
+    Ok(EncryptedData {
+        iv: bytes_to_hex(&iv),
+        ephemeral_public_key: bytes_to_hex(ephemeral_public_key),
+        cipher_data: bytes_to_hex(&cipher_data),
+        mac: bytes_to_hex(&mac),
+    })
+}
+
+pub(crate) fn open(encrypted: &EncryptedData, shared_secret: &[u8]) -> Result<Vec<u8>, String> {
+    let iv = hex_to_bytes(&encrypted.iv)?;
+    let ephemeral_pk = hex_to_bytes(&encrypted.ephemeral_public_key)?;
+    let cipher_data = hex_to_bytes(&encrypted.cipher_data)?;
+    let mac = hex_to_bytes(&encrypted.mac)?;
+
+    let (encryption_key, mac_key) = derive_ecies_keys(shared_secret);
+
+    if ecies_mac(&mac_key, &iv, &ephemeral_pk, &cipher_data)
+        .verify_slice(&mac)
+        .is_err()
+    {
+        return Err("BadMAC".to_string());
+    }
+
+    Aes256CbcDec::new_from_slices(&encryption_key, &iv)
+        .map_err(|e| format!("Failed to initialize AES: {}", e))?
+        .decrypt_padded_vec_mut::<Pkcs7>(&cipher_data)
+        .map_err(|_| "Failed to decrypt cipher data".to_string())
+}
+
+pub fn encrypt_ecies(data: &[u8], public_key_hex: &str) -> Result<EncryptedData, String> {
+    unsafe {
+        let pk_bytes = hex_to_bytes(public_key_hex)?;
+        let public_key = deserialize_g2(&pk_bytes)?;
+
+        let mut ephemeral_sk: mclBnFr = mem::zeroed();
+        if mclBnFr_setByCSPRNG(&mut ephemeral_sk) != 0 {
+            return Err("Failed to generate ephemeral key".to_string());
+        }
+        let ephemeral_pk = serialize_g2(&derive_public_key_g2(&ephemeral_sk));
+
+        let mut shared_point: mclBnG2 = mem::zeroed();
+        mclBnG2_mul(&mut shared_point, &public_key, &ephemeral_sk);
+
+        seal(data, &serialize_g2(&shared_point), &ephemeral_pk)
+    }
+}
+
+pub fn decrypt_ecies(encrypted: &EncryptedData, secret_key_hex: &str) -> Result<Vec<u8>, String> {
+    unsafe {
+        let sk_bytes = hex_to_bytes(secret_key_hex)?;
+        let secret_key = deserialize_fr(&sk_bytes)?;
+
+        let ephemeral_bytes = hex_to_bytes(&encrypted.ephemeral_public_key)?;
+        let ephemeral_pk = deserialize_g2(&ephemeral_bytes)?;
+
+        let mut shared_point: mclBnG2 = mem::zeroed();
+        mclBnG2_mul(&mut shared_point, &ephemeral_pk, &secret_key);
+
+        decrypt_ecies_with_shared_point(encrypted, &shared_point)
+    }
+}
+
+pub fn decrypt_ecies_with_shared_point(
+    encrypted: &EncryptedData,
+    shared_point: &mclBnG2,
+) -> Result<Vec<u8>, String> {
+    open(encrypted, &serialize_g2(shared_point))
+}
@@ -0,0 +1,125 @@
+use super::bls::{derive_public_key_g2, serialize_fr, serialize_g2};
+use super::ffi::*;
+use super::utils::bytes_to_hex;
+use crate::types::KeyPair;
+use hkdf::Hkdf;
+use sha2::{Digest, Sha256};
+use std::mem;
+
+const KEYGEN_SALT: &[u8] = b"BLS-SIG-KEYGEN-SALT-";
+const OKM_LENGTH_IN_BYTE: usize = 48;
+const LAMPORT_CHUNKS: usize = 255;
+const MIN_SEED_LENGTH_IN_BYTE: usize = 32;
+
+/// OS2IP of a 48-byte big-endian string, reduced mod r.
+fn fr_from_okm(okm: &[u8; OKM_LENGTH_IN_BYTE]) -> mclBnFr {
+    // Both halves are below 2^192 < r, so they load without reduction
+    let mut high = okm[..24].to_vec();
+    let mut low = okm[24..].to_vec();
+    high.reverse();
+    low.reverse();
+    let mut shift = [0u8; 25];
+    shift[24] = 1;
+
+    unsafe {
+        let mut high_fr: mclBnFr = mem::zeroed();
+        let mut low_fr: mclBnFr = mem::zeroed();
+        let mut shift_fr: mclBnFr = mem::zeroed();
+        mclBnFr_setLittleEndian(&mut high_fr, high.as_ptr() as *const _, high.len());
+        mclBnFr_setLittleEndian(&mut low_fr, low.as_ptr() as *const _, low.len());
+        mclBnFr_setLittleEndian(&mut shift_fr, shift.as_ptr() as *const _, shift.len());
+
+        let mut shifted: mclBnFr = mem::zeroed();
+        let mut result: mclBnFr = mem::zeroed();
+        mclBnFr_mul(&mut shifted, &high_fr, &shift_fr);
+        mclBnFr_add(&mut result, &shifted, &low_fr);
+        result
+    }
+}
+
+fn hkdf_mod_r(ikm: &[u8]) -> mclBnFr {
+    let mut salt = KEYGEN_SALT.to_vec();
+    let mut ikm_padded = ikm.to_vec();
+    ikm_padded.push(0);
+
+    loop {
+        salt = Sha256::digest(&salt).to_vec();
+        let hkdf = Hkdf::<Sha256>::new(Some(&salt), &ikm_padded);
+        let mut okm = [0u8; OKM_LENGTH_IN_BYTE];
+        hkdf.expand(&(OKM_LENGTH_IN_BYTE as u16).to_be_bytes(), &mut okm)
+            .expect("48 bytes is a valid HKDF-SHA256 output length");
+
+        let sk = fr_from_okm(&okm);
+        if unsafe { mclBnFr_isZero(&sk) } == 0 {
+            return sk;
+        }
+    }
+}
+
+fn ikm_to_lamport_sk(ikm: &[u8], salt: &[u8]) -> Vec<u8> {
+    let mut okm = vec![0u8; 32 * LAMPORT_CHUNKS];
+    Hkdf::<Sha256>::new(Some(salt), ikm)
+        .expand(&[], &mut okm)
+        .expect("255 hash lengths is the HKDF-SHA256 maximum");
+    okm
+}
+
+fn parent_sk_to_lamport_pk(parent_sk: &mclBnFr, index: u32) -> Vec<u8> {
+    let salt = index.to_be_bytes();
+    let mut ikm = serialize_fr(parent_sk);
+    ikm.resize(32, 0);
+    ikm.reverse();
+    let not_ikm: Vec<u8> = ikm.iter().map(|b| !b).collect();
+
+    let mut lamport_pk = Sha256::new();
+    for lamport_sk in [
+        ikm_to_lamport_sk(&ikm, &salt),
+        ikm_to_lamport_sk(&not_ikm, &salt),
+    ] {
+        for chunk in lamport_sk.chunks(32) {
+            lamport_pk.update(Sha256::digest(chunk));
+        }
+    }
+    lamport_pk.finalize().to_vec()
+}
+
+pub fn derive_master_sk(seed: &[u8]) -> Result<mclBnFr, String> {
+    if seed.len() < MIN_SEED_LENGTH_IN_BYTE {
+        return Err("Seed must be at least 32 bytes".to_string());
+    }
+    Ok(hkdf_mod_r(seed))
+}
+
+pub fn derive_child_sk(parent_sk: &mclBnFr, index: u32) -> mclBnFr {
+    hkdf_mod_r(&parent_sk_to_lamport_pk(parent_sk, index))
+}
+
+/// Parses an EIP-2334 style path such as `m/12381/3600/0/0`.
+pub fn parse_path(path: &str) -> Result<Vec<u32>, String> {
+    let mut nodes = path.split('/');
+    if nodes.next() != Some("m") {
+        return Err("Path must start with \"m\"".to_string());
+    }
+    nodes
+        .map(|node| {
+            node.parse::<u32>()
+                .map_err(|_| format!("Invalid path index: {}", node))
+        })
+        .collect()
+}
+
+pub fn derive_sk_from_path(seed: &[u8], path: &str) -> Result<mclBnFr, String> {
+    let indices = parse_path(path)?;
+    let master = derive_master_sk(seed)?;
+    Ok(indices
+        .into_iter()
+        .fold(master, |sk, index| derive_child_sk(&sk, index)))
+}
+
+pub fn derive_keypair_hex(seed: &[u8], path: &str) -> Result<KeyPair, String> {
+    let sk = derive_sk_from_path(seed, path)?;
+    Ok(KeyPair {
+        secret_key: bytes_to_hex(&serialize_fr(&sk)),
+        public_key: bytes_to_hex(&serialize_g2(&derive_public_key_g2(&sk))),
+    })
+}
@@ -0,0 +1,278 @@
+use super::bls::{
+    derive_public_key_g1, derive_public_key_g2, deserialize_fr, serialize_g1_compressed,
+    serialize_g2,
+};
+use super::utils::{bytes_to_hex, hex_to_bytes};
+use crate::types::{Eip2335Crypto, Eip2335Keystore, Eip2335Module, KeyPair};
+use aes::cipher::{KeyIvInit, StreamCipher};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use unicode_normalization::UnicodeNormalization;
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+const VERSION: u32 = 4;
+const DKLEN: usize = 32;
+const SALT_LENGTH_IN_BYTE: usize = 32;
+const IV_LENGTH_IN_BYTE: usize = 16;
+const SECRET_LENGTH_IN_BYTE: usize = 32;
+// Refuse keystores whose scrypt parameters need more than 1 GiB
+const MAX_SCRYPT_MEMORY_IN_BYTE: u128 = 1 << 30;
+// and PBKDF2 iteration counts far above the EIP-2335 default of 2^18
+const MAX_PBKDF2_ITERATIONS: u32 = 1 << 22;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Eip2335Kdf {
+    Scrypt { n: u32, r: u32, p: u32 },
+    Pbkdf2 { c: u32 },
+}
+
+impl Default for Eip2335Kdf {
+    fn default() -> Self {
+        Eip2335Kdf::Scrypt {
+            n: 262144,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+impl Eip2335Kdf {
+    fn to_module(self, salt: &[u8]) -> Eip2335Module {
+        let (function, params) = match self {
+            Eip2335Kdf::Scrypt { n, r, p } => (
+                "scrypt",
+                json!({ "dklen": DKLEN, "n": n, "r": r, "p": p, "salt": bytes_to_hex(salt) }),
+            ),
+            Eip2335Kdf::Pbkdf2 { c } => (
+                "pbkdf2",
+                json!({ "dklen": DKLEN, "c": c, "prf": "hmac-sha256", "salt": bytes_to_hex(salt) }),
+            ),
+        };
+        module(function, params, String::new())
+    }
+
+    fn from_module(kdf: &Eip2335Module) -> Result<(Self, Vec<u8>), String> {
+        if param_u32(&kdf.params, "dklen")? as usize != DKLEN {
+            return Err("Unsupported KDF dklen".to_string());
+        }
+        let salt = hex_to_bytes(param_str(&kdf.params, "salt")?)?;
+
+        let kdf = match kdf.function.as_str() {
+            "scrypt" => Eip2335Kdf::Scrypt {
+                n: param_u32(&kdf.params, "n")?,
+                r: param_u32(&kdf.params, "r")?,
+                p: param_u32(&kdf.params, "p")?,
+            },
+            "pbkdf2" => {
+                if param_str(&kdf.params, "prf")? != "hmac-sha256" {
+                    return Err("Unsupported PBKDF2 PRF".to_string());
+                }
+                Eip2335Kdf::Pbkdf2 {
+                    c: param_u32(&kdf.params, "c")?,
+                }
+            }
+            other => return Err(format!("Unsupported KDF: {}", other)),
+        };
+        Ok((kdf, salt))
+    }
+
+    fn derive_key(self, password: &[u8], salt: &[u8]) -> Result<[u8; DKLEN], String> {
+        let mut key = [0u8; DKLEN];
+        match self {
+            Eip2335Kdf::Scrypt { n, r, p } => {
+                if !n.is_power_of_two() || n < 2 {
+                    return Err("scrypt n must be a power of two".to_string());
+                }
+                if 128 * n as u128 * r as u128 > MAX_SCRYPT_MEMORY_IN_BYTE {
+                    return Err("scrypt memory cost too high".to_string());
+                }
+                let params = scrypt::Params::new(n.trailing_zeros() as u8, r, p, DKLEN)
+                    .map_err(|e| format!("Invalid scrypt parameters: {}", e))?;
+                scrypt::scrypt(password, salt, &params, &mut key)
+                    .map_err(|e| format!("scrypt failed: {}", e))?;
+            }
+            Eip2335Kdf::Pbkdf2 { c } => {
+                if c == 0 || c > MAX_PBKDF2_ITERATIONS {
+                    return Err("PBKDF2 iteration count out of range".to_string());
+                }
+                pbkdf2::pbkdf2_hmac::<Sha256>(password, salt, c, &mut key);
+            }
+        }
+        Ok(key)
+    }
+}
+
+fn module(function: &str, params: Value, message: String) -> Eip2335Module {
+    Eip2335Module {
+        function: function.to_string(),
+        params: match params {
+            Value::Object(params) => params,
+            _ => Map::new(),
+        },
+        message,
+    }
+}
+
+fn param_str<'a>(params: &'a Map<String, Value>, name: &str) -> Result<&'a str, String> {
+    params
+        .get(name)
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("Missing KDF parameter: {}", name))
+}
+
+fn param_u32(params: &Map<String, Value>, name: &str) -> Result<u32, String> {
+    params
+        .get(name)
+        .and_then(Value::as_u64)
+        .and_then(|v| u32::try_from(v).ok())
+        .ok_or_else(|| format!("Missing KDF parameter: {}", name))
+}
+
+/// NFKD-normalizes the password and strips C0, C1 and DEL control codes.
+fn process_password(password: &str) -> Vec<u8> {
+    password
+        .nfkd()
+        .filter(|c| !matches!(*c as u32, 0x00..=0x1f | 0x7f..=0x9f))
+        .collect::<String>()
+        .into_bytes()
+}
+
+fn checksum(decryption_key: &[u8], cipher_message: &[u8]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(&decryption_key[16..32]);
+    hasher.update(cipher_message);
+    hasher.finalize().to_vec()
+}
+
+fn apply_cipher(decryption_key: &[u8], iv: &[u8], data: &mut [u8]) -> Result<(), String> {
+    Aes128Ctr::new_from_slices(&decryption_key[..16], iv)
+        .map_err(|e| format!("Failed to initialize AES: {}", e))?
+        .apply_keystream(data);
+    Ok(())
+}
+
+// EIP-2335 stores the scalar big-endian; the crate's hex keys are little-endian.
+fn to_big_endian(secret_key: &str) -> Result<Vec<u8>, String> {
+    let mut secret = hex_to_bytes(secret_key)?;
+    if secret.len() != SECRET_LENGTH_IN_BYTE {
+        return Err("Secret key must be 32 bytes".to_string());
+    }
+    secret.reverse();
+    Ok(secret)
+}
+
+// Returns the crate key pair and the EIP-2335 `pubkey`, the compressed sk·G1.
+fn from_big_endian(secret: &[u8]) -> Result<(KeyPair, String), String> {
+    if secret.len() != SECRET_LENGTH_IN_BYTE {
+        return Err("Keystore secret must be 32 bytes".to_string());
+    }
+    let mut little_endian = secret.to_vec();
+    little_endian.reverse();
+    let fr = deserialize_fr(&little_endian)?;
+
+    let key_pair = KeyPair {
+        secret_key: bytes_to_hex(&little_endian),
+        public_key: bytes_to_hex(&serialize_g2(&derive_public_key_g2(&fr))),
+    };
+    let pubkey = bytes_to_hex(&serialize_g1_compressed(&derive_public_key_g1(&fr))?);
+    Ok((key_pair, pubkey))
+}
+
+/// Exports a secret key (a `KeyPair.secret_key` or an `ActorShare.sh`) as
+/// an EIP-2335 keystore. `pubkey` is the G1 key Ethereum tools expect, not
+/// the crate's G2 public key.
+pub fn export_eip2335(
+    secret_key: &str,
+    password: &str,
+    path: &str,
+    kdf: Eip2335Kdf,
+) -> Result<Eip2335Keystore, String> {
+    let secret = to_big_endian(secret_key)?;
+    // Rejects secrets that are not valid scalars
+    let (_, pubkey) = from_big_endian(&secret)?;
+
+    let mut salt = [0u8; SALT_LENGTH_IN_BYTE];
+    let mut iv = [0u8; IV_LENGTH_IN_BYTE];
+    let mut uuid = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut iv);
+    OsRng.fill_bytes(&mut uuid);
+
+    let decryption_key = kdf.derive_key(&process_password(password), &salt)?;
+    let mut cipher_message = secret;
+    apply_cipher(&decryption_key, &iv, &mut cipher_message)?;
+
+    Ok(Eip2335Keystore {
+        crypto: Eip2335Crypto {
+            kdf: kdf.to_module(&salt),
+            checksum: module(
+                "sha256",
+                json!({}),
+                bytes_to_hex(&checksum(&decryption_key, &cipher_message)),
+            ),
+            cipher: module(
+                "aes-128-ctr",
+                json!({ "iv": bytes_to_hex(&iv) }),
+                bytes_to_hex(&cipher_message),
+            ),
+        },
+        description: String::new(),
+        pubkey,
+        path: path.to_string(),
+        uuid: format_uuid_v4(uuid),
+        version: VERSION,
+    })
+}
+
+/// Decrypts an EIP-2335 keystore into a crate key pair.
+pub fn import_eip2335(keystore: &Eip2335Keystore, password: &str) -> Result<KeyPair, String> {
+    if keystore.version != VERSION {
+        return Err(format!(
+            "Unsupported keystore version: {}",
+            keystore.version
+        ));
+    }
+    let crypto = &keystore.crypto;
+    if crypto.checksum.function != "sha256" {
+        return Err(format!(
+            "Unsupported checksum: {}",
+            crypto.checksum.function
+        ));
+    }
+    if crypto.cipher.function != "aes-128-ctr" {
+        return Err(format!("Unsupported cipher: {}", crypto.cipher.function));
+    }
+
+    let (kdf, salt) = Eip2335Kdf::from_module(&crypto.kdf)?;
+    let decryption_key = kdf.derive_key(&process_password(password), &salt)?;
+
+    let mut secret = hex_to_bytes(&crypto.cipher.message)?;
+    if bytes_to_hex(&checksum(&decryption_key, &secret)) != crypto.checksum.message.to_lowercase() {
+        return Err("Invalid password or corrupted keystore".to_string());
+    }
+    let iv = hex_to_bytes(param_str(&crypto.cipher.params, "iv")?)?;
+    apply_cipher(&decryption_key, &iv, &mut secret)?;
+
+    let (key_pair, pubkey) = from_big_endian(&secret)?;
+    if !keystore.pubkey.is_empty() && !keystore.pubkey.eq_ignore_ascii_case(&pubkey) {
+        return Err("Keystore pubkey does not match its secret".to_string());
+    }
+    Ok(key_pair)
+}
+
+fn format_uuid_v4(mut bytes: [u8; 16]) -> String {
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = bytes_to_hex(&bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
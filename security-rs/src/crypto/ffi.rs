@@ -1,4 +1,6 @@
-use std::os::raw::{c_int, c_void};
+use std::os::raw::c_int;
+#[cfg(feature = "mcl")]
+use std::os::raw::c_void;
 
 #[repr(C)]
 #[derive(Copy, Clone)]
@@ -63,6 +65,10 @@ pub struct BlsId {
 pub const MCL_BLS12_381: c_int = 5;
 pub const MCLBN_COMPILED_TIME_VAR: c_int = 46;
 
+#[cfg(all(feature = "pure-rust", not(feature = "mcl")))]
+pub use super::backend::abi::*;
+
+#[cfg(feature = "mcl")]
 #[link(name = "mcl", kind = "static")]
 unsafe extern "C" {
     pub fn mclBn_init(curve: c_int, compiledTimeVar: c_int) -> c_int;
@@ -73,7 +79,11 @@ unsafe extern "C" {
     pub fn mclBnFr_setHashOf(x: *mut mclBnFr, buf: *const c_void, bufSize: usize) -> c_int;
     pub fn mclBnFr_deserialize(x: *mut mclBnFr, buf: *const c_void, bufSize: usize) -> usize;
     pub fn mclBnFr_serialize(buf: *mut c_void, maxBufSize: usize, x: *const mclBnFr) -> usize;
+    pub fn mclBnFr_setLittleEndian(x: *mut mclBnFr, buf: *const c_void, bufSize: usize) -> c_int;
+    pub fn mclBnFr_getLittleEndian(buf: *mut c_void, maxBufSize: usize, x: *const mclBnFr)
+        -> usize;
     pub fn mclBnFr_isEqual(x: *const mclBnFr, y: *const mclBnFr) -> c_int;
+    pub fn mclBnFr_isZero(x: *const mclBnFr) -> c_int;
     pub fn mclBnFr_add(z: *mut mclBnFr, x: *const mclBnFr, y: *const mclBnFr);
     pub fn mclBnFr_sub(z: *mut mclBnFr, x: *const mclBnFr, y: *const mclBnFr);
     pub fn mclBnFr_mul(z: *mut mclBnFr, x: *const mclBnFr, y: *const mclBnFr);
@@ -82,14 +92,20 @@ unsafe extern "C" {
     pub fn mclBnG1_deserialize(x: *mut mclBnG1, buf: *const c_void, bufSize: usize) -> usize;
     pub fn mclBnG1_serialize(buf: *mut c_void, maxBufSize: usize, x: *const mclBnG1) -> usize;
     pub fn mclBnG1_hashAndMapTo(x: *mut mclBnG1, buf: *const c_void, bufSize: usize) -> c_int;
+    pub fn mclBnFp_setLittleEndianMod(x: *mut mclBnFp, buf: *const c_void, bufSize: usize)
+        -> c_int;
+    pub fn mclBnFp_mapToG1(y: *mut mclBnG1, x: *const mclBnFp) -> c_int;
     pub fn mclBnG1_mul(z: *mut mclBnG1, x: *const mclBnG1, y: *const mclBnFr);
     pub fn mclBnG1_add(z: *mut mclBnG1, x: *const mclBnG1, y: *const mclBnG1);
     pub fn mclBnG1_isEqual(x: *const mclBnG1, y: *const mclBnG1) -> c_int;
+    pub fn mclBnG1_normalize(y: *mut mclBnG1, x: *const mclBnG1);
+    pub fn mclBnFp_serialize(buf: *mut c_void, maxBufSize: usize, x: *const mclBnFp) -> usize;
     pub fn mclBnG2_deserialize(x: *mut mclBnG2, buf: *const c_void, bufSize: usize) -> usize;
     pub fn mclBnG2_serialize(buf: *mut c_void, maxBufSize: usize, x: *const mclBnG2) -> usize;
     pub fn mclBnG2_mul(z: *mut mclBnG2, x: *const mclBnG2, y: *const mclBnFr);
     pub fn mclBnG2_add(z: *mut mclBnG2, x: *const mclBnG2, y: *const mclBnG2);
     pub fn mclBnG2_isEqual(x: *const mclBnG2, y: *const mclBnG2) -> c_int;
+    pub fn mclBnG2_isZero(x: *const mclBnG2) -> c_int;
     pub fn mclBnGT_mul(z: *mut mclBnGT, x: *const mclBnGT, y: *const mclBnGT);
     pub fn mclBnGT_isEqual(x: *const mclBnGT, y: *const mclBnGT) -> c_int;
     pub fn mclBnGT_serialize(buf: *mut c_void, maxBufSize: usize, x: *const mclBnGT) -> usize;
@@ -174,10 +190,23 @@ unsafe extern "C" {
         buf: *const c_void,
         bufSize: usize,
     ) -> usize;
+    pub fn blsSignatureRecover(
+        sig: *mut BlsSignature,
+        sigVec: *const BlsSignature,
+        idVec: *const BlsId,
+        n: usize,
+    ) -> c_int;
+    pub fn blsVerify(
+        sig: *const BlsSignature,
+        pub_key: *const BlsPublicKey,
+        msg: *const c_void,
+        msgSize: usize,
+    ) -> c_int;
     pub fn blsIdDeserialize(id: *mut BlsId, buf: *const c_void, bufSize: usize) -> usize;
 }
 
 pub const FR_SIZE: usize = 32;
+pub const FP_SIZE: usize = 48;
 pub const G1_SIZE: usize = 48;
 pub const G2_SIZE: usize = 96;
 pub const GT_SIZE: usize = 576;
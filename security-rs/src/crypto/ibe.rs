@@ -0,0 +1,170 @@
+use super::bls::{
+    derive_public_key_g2, deserialize_fr, deserialize_g1, deserialize_g2, get_g2_generator,
+    map_to_g1, serialize_g1, serialize_g2, serialize_gt,
+};
+use super::ecies::{open, seal};
+use super::ffi::*;
+use super::pairing;
+use super::utils::{bytes_to_hex, hex_to_bytes};
+use crate::types::{ActorShare, EncryptedData, IdentityKeyShare, PublicShare};
+use sha2::{Digest, Sha512};
+use std::mem;
+
+const IBE_IDENTITY_DST: &[u8] = b"SECURITY-RS-IBE-IDENTITY-V2";
+
+// Identity keys are signing-share multiples of this point, so it must not be
+// a point signing hashes to. Signing maps a masked 48-byte prefix of a digest
+// onto G1; identities map the whole 64-byte digest reduced mod p, which no
+// message reaches without a SHA-512 preimage.
+fn hash_identity(identity: &str) -> Result<mclBnG1, String> {
+    let mut hasher = Sha512::new();
+    hasher.update(IBE_IDENTITY_DST);
+    hasher.update(identity.as_bytes());
+    map_to_g1(&hasher.finalize())
+}
+
+pub fn encrypt_ibe(
+    data: &[u8],
+    identity: &str,
+    group_key_hex: &str,
+) -> Result<EncryptedData, String> {
+    unsafe {
+        let group_key = deserialize_g2(&hex_to_bytes(group_key_hex)?)?;
+        let q = hash_identity(identity)?;
+
+        let mut r: mclBnFr = mem::zeroed();
+        if mclBnFr_setByCSPRNG(&mut r) != 0 {
+            return Err("Failed to generate ephemeral key".to_string());
+        }
+        let u = serialize_g2(&derive_public_key_g2(&r));
+
+        let mut group_key_r: mclBnG2 = mem::zeroed();
+        mclBnG2_mul(&mut group_key_r, &group_key, &r);
+        let shared = pairing(&q, &group_key_r);
+
+        seal(data, &serialize_gt(&shared), &u)
+    }
+}
+
+pub fn decrypt_ibe(encrypted: &EncryptedData, identity_key_hex: &str) -> Result<Vec<u8>, String> {
+    let identity_key = deserialize_g1(&hex_to_bytes(identity_key_hex)?)?;
+    let u = deserialize_g2(&hex_to_bytes(&encrypted.ephemeral_public_key)?)?;
+    let shared = pairing(&identity_key, &u);
+
+    open(encrypted, &serialize_gt(&shared))
+}
+
+pub fn generate_identity_key_share(
+    actor_share: &ActorShare,
+    identity: &str,
+) -> Result<IdentityKeyShare, String> {
+    let member_id = actor_share.resolved_member_id()?;
+    if actor_share.recovery_only {
+        return Err("Recovery-only shares cannot derive identity keys".to_string());
+    }
+
+    unsafe {
+        let sh = deserialize_fr(&hex_to_bytes(&actor_share.sh)?)?;
+        let q = hash_identity(identity)?;
+
+        let mut key: mclBnG1 = mem::zeroed();
+        mclBnG1_mul(&mut key, &q, &sh);
+
+        Ok(IdentityKeyShare {
+            id: member_id.to_string(),
+            key: bytes_to_hex(&serialize_g1(&key)),
+        })
+    }
+}
+
+pub fn verify_identity_key_share(
+    share: &IdentityKeyShare,
+    public_share: &PublicShare,
+    identity: &str,
+) -> Result<(), String> {
+    if share.id != public_share.id {
+        return Err("Identity key share id does not match public share id".to_string());
+    }
+
+    let key = deserialize_g1(&hex_to_bytes(&share.key)?)?;
+    let ph = deserialize_g2(&hex_to_bytes(&public_share.ph)?)?;
+    let q = hash_identity(identity)?;
+
+    let lhs = pairing(&key, &get_g2_generator());
+    let rhs = pairing(&q, &ph);
+    if unsafe { mclBnGT_isEqual(&lhs, &rhs) } == 0 {
+        return Err("INVALID_IDENTITY_KEY_SHARE".to_string());
+    }
+
+    Ok(())
+}
+
+pub fn combine_identity_key_shares(
+    identity: &str,
+    threshold: usize,
+    shares: &[IdentityKeyShare],
+    public_shares: &[PublicShare],
+) -> Result<String, String> {
+    if threshold == 0 {
+        return Err("Threshold must be at least 1".to_string());
+    }
+
+    unsafe {
+        let mut bls_ids: Vec<BlsId> = Vec::new();
+        let mut bls_keys: Vec<BlsSignature> = Vec::new();
+        let mut rejected: Vec<String> = Vec::new();
+
+        for share in shares {
+            if bls_ids.len() == threshold {
+                break;
+            }
+
+            let public_share = match public_shares.iter().find(|ph| ph.id == share.id) {
+                Some(public_share) => public_share,
+                None => {
+                    rejected.push(share.id.clone());
+                    continue;
+                }
+            };
+            if verify_identity_key_share(share, public_share, identity).is_err() {
+                rejected.push(share.id.clone());
+                continue;
+            }
+
+            let id = deserialize_fr(&hex_to_bytes(&share.id)?)?;
+            if bls_ids
+                .iter()
+                .any(|bls_id| mclBnFr_isEqual(&bls_id.v, &id) != 0)
+            {
+                continue;
+            }
+
+            bls_ids.push(BlsId { v: id });
+            bls_keys.push(BlsSignature {
+                v: deserialize_g1(&hex_to_bytes(&share.key)?)?,
+            });
+        }
+
+        if bls_ids.len() < threshold {
+            return Err(format!(
+                "Not enough valid identity key shares: got {}, need {} (rejected: {})",
+                bls_ids.len(),
+                threshold,
+                rejected.join(", ")
+            ));
+        }
+
+        let mut identity_key: BlsSignature = mem::zeroed();
+        let ret = blsSignatureRecover(
+            &mut identity_key,
+            bls_keys.as_ptr(),
+            bls_ids.as_ptr(),
+            bls_ids.len(),
+        );
+        if ret != 0 {
+            return Err("blsSignatureRecover failed for identity key shares".to_string());
+        }
+
+        Ok(bytes_to_hex(&serialize_g1(&identity_key.v)))
+    }
+}
@@ -0,0 +1,30 @@
+use bip39::Mnemonic;
+use rand::rngs::OsRng;
+use rand::RngCore;
+
+/// Generates an English BIP-39 mnemonic of 12, 15, 18, 21 or 24 words.
+pub fn generate_mnemonic(word_count: usize) -> Result<String, String> {
+    if !(12..=24).contains(&word_count) || word_count % 3 != 0 {
+        return Err("Word count must be 12, 15, 18, 21 or 24".to_string());
+    }
+
+    let mut entropy = vec![0u8; word_count / 3 * 4];
+    OsRng.fill_bytes(&mut entropy);
+    Mnemonic::from_entropy(&entropy)
+        .map(|mnemonic| mnemonic.to_string())
+        .map_err(|e| format!("Failed to generate mnemonic: {}", e))
+}
+
+/// Checks the words against the English list and the checksum.
+pub fn validate_mnemonic(phrase: &str) -> Result<(), String> {
+    Mnemonic::parse(phrase)
+        .map(|_| ())
+        .map_err(|e| format!("Invalid mnemonic: {}", e))
+}
+
+/// BIP-39 seed (PBKDF2-HMAC-SHA512, 2048 rounds) of a valid mnemonic.
+pub fn mnemonic_to_seed(phrase: &str, passphrase: &str) -> Result<Vec<u8>, String> {
+    Mnemonic::parse(phrase)
+        .map(|mnemonic| mnemonic.to_seed(passphrase).to_vec())
+        .map_err(|e| format!("Invalid mnemonic: {}", e))
+}
@@ -1,15 +1,52 @@
 pub mod ffi;
+pub mod backend;
+pub mod beacon;
 pub mod bls;
+pub mod ecies;
+pub mod eip2333;
+pub mod eip2335;
+pub mod ibe;
+pub mod mnemonic;
+pub mod nizk;
 pub mod pvsh;
 pub mod secret_sharing;
 pub mod threshold;
 pub mod utils;
 
+pub use beacon::{
+    beacon_message, beacon_randomness, combine_beacon_partials, generate_beacon_partial,
+    verify_beacon_round,
+};
 pub use bls::{
-    derive_public_key_g2, deserialize_fr, deserialize_g1, deserialize_g2, generate_id_hex,
-    generate_keypair_hex, get_g2_generator, hash_to_fr, hash_to_g1, init_bls, pairing,
-    serialize_fr, serialize_g1, serialize_g2, sign, sign_direct,
+    derive_public_key_g1, derive_public_key_g2, deserialize_fr, deserialize_g1, deserialize_g2,
+    generate_id_hex, generate_keypair_hex, get_g1_generator, get_g2_generator, hash_to_fr,
+    hash_to_g1, init_bls, pairing, serialize_fr, serialize_g1, serialize_g1_compressed,
+    serialize_g2, sign, sign_direct, verify,
+};
+pub use ecies::{decrypt_ecies, decrypt_ecies_with_shared_point, encrypt_ecies};
+pub use eip2333::{
+    derive_child_sk, derive_keypair_hex, derive_master_sk, derive_sk_from_path, parse_path,
+};
+pub use eip2335::{export_eip2335, import_eip2335, Eip2335Kdf};
+pub use ibe::{
+    combine_identity_key_shares, decrypt_ibe, encrypt_ibe, generate_identity_key_share,
+    verify_identity_key_share,
 };
+pub use mnemonic::{generate_mnemonic, mnemonic_to_seed, validate_mnemonic};
+pub use nizk::{prove_dleq, prove_dlog, verify_dleq, verify_dlog, NizkGroup};
 pub use pvsh::{pvsh_decode_g2, pvsh_encode_g2, pvsh_verify_g2};
-pub use secret_sharing::{fr_evaluate_polynomial, fr_lagrange_interpolation};
-pub use threshold::{calculate_threshold_keys, generate_actor_share, generate_contribution};
+pub use secret_sharing::{
+    combine_secret_bytes, feldman_reconstruct, feldman_split, feldman_verify_share,
+    fr_evaluate_polynomial, fr_lagrange_coefficient, fr_lagrange_interpolation,
+    fr_lagrange_interpolation_at, g2_evaluate_polynomial, split_secret_bytes, FeldmanSharing,
+};
+pub use threshold::{
+    calculate_threshold_keys, combine_decryption_shares, combine_partial_signatures,
+    compute_public_shares, generate_actor_share, generate_contribution, generate_decryption_share,
+    generate_recovered_actor_share, generate_recovery_contribution, generate_refresh_contribution,
+    generate_repair_contribution, generate_repair_share, generate_reshare_contribution,
+    generate_reshared_actor_share, recover_repaired_actor_share, refresh_actor_share,
+    threshold_key_from_actor_share, threshold_key_from_bytes, threshold_key_to_bytes,
+    verify_decryption_share, verify_partial, verify_repair_contribution,
+    verify_reshare_contribution,
+};
@@ -0,0 +1,218 @@
+use super::bls::{deserialize_fr, serialize_fr, serialize_g1, serialize_g2};
+use super::ffi::*;
+use super::hash_to_fr;
+use super::utils::{bytes_to_hex, hex_to_bytes};
+use std::mem;
+
+const SCHNORR_LABEL: &[u8] = b"schnorr-dlog";
+const DLEQ_LABEL: &[u8] = b"chaum-pedersen-dleq";
+
+pub trait NizkGroup: Copy {
+    const TAG: u8;
+
+    fn mul(&self, scalar: &mclBnFr) -> Self;
+    fn add(&self, other: &Self) -> Self;
+    fn to_bytes(&self) -> Vec<u8>;
+}
+
+impl NizkGroup for mclBnG1 {
+    const TAG: u8 = 1;
+
+    fn mul(&self, scalar: &mclBnFr) -> Self {
+        unsafe {
+            let mut result: mclBnG1 = mem::zeroed();
+            mclBnG1_mul(&mut result, self, scalar);
+            result
+        }
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        unsafe {
+            let mut result: mclBnG1 = mem::zeroed();
+            mclBnG1_add(&mut result, self, other);
+            result
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        serialize_g1(self)
+    }
+}
+
+impl NizkGroup for mclBnG2 {
+    const TAG: u8 = 2;
+
+    fn mul(&self, scalar: &mclBnFr) -> Self {
+        unsafe {
+            let mut result: mclBnG2 = mem::zeroed();
+            mclBnG2_mul(&mut result, self, scalar);
+            result
+        }
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        unsafe {
+            let mut result: mclBnG2 = mem::zeroed();
+            mclBnG2_add(&mut result, self, other);
+            result
+        }
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        serialize_g2(self)
+    }
+}
+
+struct Transcript {
+    bytes: Vec<u8>,
+}
+
+impl Transcript {
+    fn new(domain: &[u8], label: &[u8]) -> Self {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&(domain.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(domain);
+        bytes.extend_from_slice(&(label.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(label);
+        Transcript { bytes }
+    }
+
+    fn append<G: NizkGroup>(&mut self, point: &G) {
+        self.bytes.push(G::TAG);
+        self.bytes.extend_from_slice(&point.to_bytes());
+    }
+
+    fn challenge(&self) -> Result<mclBnFr, String> {
+        hash_to_fr(&self.bytes)
+    }
+}
+
+fn random_nonce() -> Result<mclBnFr, String> {
+    unsafe {
+        let mut k: mclBnFr = mem::zeroed();
+        if mclBnFr_setByCSPRNG(&mut k) != 0 {
+            return Err("Failed to generate proof nonce".to_string());
+        }
+        Ok(k)
+    }
+}
+
+fn response(k: &mclBnFr, c: &mclBnFr, secret: &mclBnFr) -> mclBnFr {
+    unsafe {
+        let mut c_secret: mclBnFr = mem::zeroed();
+        mclBnFr_mul(&mut c_secret, c, secret);
+        let mut z: mclBnFr = mem::zeroed();
+        mclBnFr_sub(&mut z, k, &c_secret);
+        z
+    }
+}
+
+fn encode_proof(c: &mclBnFr, z: &mclBnFr) -> String {
+    format!(
+        "{}.{}",
+        bytes_to_hex(&serialize_fr(c)),
+        bytes_to_hex(&serialize_fr(z))
+    )
+}
+
+fn decode_proof(proof: &str) -> Result<(mclBnFr, mclBnFr), String> {
+    let parts: Vec<&str> = proof.split('.').collect();
+    if parts.len() != 2 {
+        return Err("Invalid proof format".to_string());
+    }
+    let c = deserialize_fr(&hex_to_bytes(parts[0])?)?;
+    let z = deserialize_fr(&hex_to_bytes(parts[1])?)?;
+    Ok((c, z))
+}
+
+fn is_challenge_equal(c: &mclBnFr, expected: &mclBnFr) -> bool {
+    unsafe { mclBnFr_isEqual(c, expected) != 0 }
+}
+
+pub fn prove_dlog<G: NizkGroup>(
+    domain: &[u8],
+    base: &G,
+    secret: &mclBnFr,
+) -> Result<String, String> {
+    let public = base.mul(secret);
+    let k = random_nonce()?;
+    let a = base.mul(&k);
+
+    let mut transcript = Transcript::new(domain, SCHNORR_LABEL);
+    transcript.append(base);
+    transcript.append(&public);
+    transcript.append(&a);
+    let c = transcript.challenge()?;
+
+    Ok(encode_proof(&c, &response(&k, &c, secret)))
+}
+
+pub fn verify_dlog<G: NizkGroup>(
+    domain: &[u8],
+    base: &G,
+    public: &G,
+    proof: &str,
+) -> Result<(), String> {
+    let (c, z) = decode_proof(proof)?;
+    let a = base.mul(&z).add(&public.mul(&c));
+
+    let mut transcript = Transcript::new(domain, SCHNORR_LABEL);
+    transcript.append(base);
+    transcript.append(public);
+    transcript.append(&a);
+
+    if !is_challenge_equal(&c, &transcript.challenge()?) {
+        return Err("INVALID_DLOG_PROOF".to_string());
+    }
+    Ok(())
+}
+
+pub fn prove_dleq<A: NizkGroup, B: NizkGroup>(
+    domain: &[u8],
+    base_a: &A,
+    base_b: &B,
+    secret: &mclBnFr,
+) -> Result<String, String> {
+    let public_a = base_a.mul(secret);
+    let public_b = base_b.mul(secret);
+    let k = random_nonce()?;
+    let commitment_a = base_a.mul(&k);
+    let commitment_b = base_b.mul(&k);
+
+    let mut transcript = Transcript::new(domain, DLEQ_LABEL);
+    transcript.append(base_a);
+    transcript.append(&public_a);
+    transcript.append(base_b);
+    transcript.append(&public_b);
+    transcript.append(&commitment_a);
+    transcript.append(&commitment_b);
+    let c = transcript.challenge()?;
+
+    Ok(encode_proof(&c, &response(&k, &c, secret)))
+}
+
+pub fn verify_dleq<A: NizkGroup, B: NizkGroup>(
+    domain: &[u8],
+    base_a: &A,
+    public_a: &A,
+    base_b: &B,
+    public_b: &B,
+    proof: &str,
+) -> Result<(), String> {
+    let (c, z) = decode_proof(proof)?;
+    let commitment_a = base_a.mul(&z).add(&public_a.mul(&c));
+    let commitment_b = base_b.mul(&z).add(&public_b.mul(&c));
+
+    let mut transcript = Transcript::new(domain, DLEQ_LABEL);
+    transcript.append(base_a);
+    transcript.append(public_a);
+    transcript.append(base_b);
+    transcript.append(public_b);
+    transcript.append(&commitment_a);
+    transcript.append(&commitment_b);
+
+    if !is_challenge_equal(&c, &transcript.challenge()?) {
+        return Err("INVALID_DLEQ_PROOF".to_string());
+    }
+    Ok(())
+}
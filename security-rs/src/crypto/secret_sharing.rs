@@ -1,4 +1,9 @@
+use super::bls::{derive_public_key_g2, deserialize_fr, serialize_fr};
 use super::ffi::*;
+use super::utils::{bytes_to_hex, hex_to_bytes};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
 
 pub fn fr_evaluate_polynomial(coefficients: &[mclBnFr], x: &mclBnFr) -> Result<mclBnFr, String> {
     if coefficients.is_empty() {
@@ -46,3 +51,383 @@ pub fn fr_lagrange_interpolation(x_vec: &[mclBnFr], y_vec: &[mclBnFr]) -> Result
         }
     }
 }
+
+pub fn g2_evaluate_polynomial(commitments: &[mclBnG2], x: &mclBnFr) -> Result<mclBnG2, String> {
+    if commitments.is_empty() {
+        return Err("Commitments vector cannot be empty".to_string());
+    }
+
+    unsafe {
+        let mut result = commitments[commitments.len() - 1];
+        for commitment in commitments[..commitments.len() - 1].iter().rev() {
+            let mut scaled: mclBnG2 = std::mem::zeroed();
+            mclBnG2_mul(&mut scaled, &result, x);
+            mclBnG2_add(&mut result, &scaled, commitment);
+        }
+        Ok(result)
+    }
+}
+
+pub fn fr_lagrange_coefficient(
+    x_vec: &[mclBnFr],
+    index: usize,
+    x: &mclBnFr,
+) -> Result<mclBnFr, String> {
+    if index >= x_vec.len() {
+        return Err("Lagrange index out of range".to_string());
+    }
+
+    unsafe {
+        let mut numerator: mclBnFr = std::mem::zeroed();
+        mclBnFr_setInt(&mut numerator, 1);
+        let mut denominator = numerator;
+
+        for (j, x_j) in x_vec.iter().enumerate() {
+            if j == index {
+                continue;
+            }
+
+            let mut diff: mclBnFr = std::mem::zeroed();
+            mclBnFr_sub(&mut diff, x, x_j);
+            let mut product: mclBnFr = std::mem::zeroed();
+            mclBnFr_mul(&mut product, &numerator, &diff);
+            numerator = product;
+
+            mclBnFr_sub(&mut diff, &x_vec[index], x_j);
+            if mclBnFr_isZero(&diff) != 0 {
+                return Err("Duplicate x values in Lagrange interpolation".to_string());
+            }
+            mclBnFr_mul(&mut product, &denominator, &diff);
+            denominator = product;
+        }
+
+        let mut result: mclBnFr = std::mem::zeroed();
+        mclBnFr_div(&mut result, &numerator, &denominator);
+        Ok(result)
+    }
+}
+
+pub fn fr_lagrange_interpolation_at(
+    x_vec: &[mclBnFr],
+    y_vec: &[mclBnFr],
+    x: &mclBnFr,
+) -> Result<mclBnFr, String> {
+    if x_vec.len() != y_vec.len() {
+        return Err("x_vec and y_vec must have same length".to_string());
+    }
+    if x_vec.is_empty() {
+        return Err("Input vectors cannot be empty".to_string());
+    }
+
+    unsafe {
+        let mut result: mclBnFr = std::mem::zeroed();
+        for (i, y) in y_vec.iter().enumerate() {
+            let coefficient = fr_lagrange_coefficient(x_vec, i, x)?;
+            let mut term: mclBnFr = std::mem::zeroed();
+            mclBnFr_mul(&mut term, &coefficient, y);
+            let mut sum: mclBnFr = std::mem::zeroed();
+            mclBnFr_add(&mut sum, &result, &term);
+            result = sum;
+        }
+        Ok(result)
+    }
+}
+
+pub struct FeldmanSharing {
+    pub commitments: Vec<mclBnG2>,
+    pub shares: Vec<mclBnFr>,
+}
+
+fn check_share_ids(ids: &[mclBnFr]) -> Result<(), String> {
+    unsafe {
+        for (i, id) in ids.iter().enumerate() {
+            if mclBnFr_isZero(id) != 0 {
+                return Err("Share id cannot be zero".to_string());
+            }
+            if ids[..i].iter().any(|other| mclBnFr_isEqual(other, id) != 0) {
+                return Err("Duplicate share id".to_string());
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn feldman_split(
+    secret: &mclBnFr,
+    threshold: usize,
+    ids: &[mclBnFr],
+) -> Result<FeldmanSharing, String> {
+    if threshold == 0 {
+        return Err("Threshold must be at least 1".to_string());
+    }
+    if threshold > ids.len() {
+        return Err("Threshold cannot exceed number of shares".to_string());
+    }
+    check_share_ids(ids)?;
+
+    unsafe {
+        let mut coefficients = vec![*secret];
+        for _ in 1..threshold {
+            let mut coefficient: mclBnFr = std::mem::zeroed();
+            if mclBnFr_setByCSPRNG(&mut coefficient) != 0 {
+                return Err("Failed to generate random coefficient".to_string());
+            }
+            coefficients.push(coefficient);
+        }
+
+        let commitments = coefficients.iter().map(derive_public_key_g2).collect();
+        let shares = ids
+            .iter()
+            .map(|id| fr_evaluate_polynomial(&coefficients, id))
+            .collect::<Result<Vec<mclBnFr>, String>>()?;
+
+        Ok(FeldmanSharing {
+            commitments,
+            shares,
+        })
+    }
+}
+
+pub fn feldman_verify_share(
+    commitments: &[mclBnG2],
+    id: &mclBnFr,
+    share: &mclBnFr,
+) -> Result<(), String> {
+    let expected = g2_evaluate_polynomial(commitments, id)?;
+    let actual = derive_public_key_g2(share);
+
+    unsafe {
+        if mclBnG2_isEqual(&expected, &actual) == 0 {
+            return Err("Share does not match commitments".to_string());
+        }
+    }
+    Ok(())
+}
+
+pub fn feldman_reconstruct(
+    threshold: usize,
+    ids: &[mclBnFr],
+    shares: &[mclBnFr],
+) -> Result<mclBnFr, String> {
+    if ids.len() != shares.len() {
+        return Err("ids and shares must have same length".to_string());
+    }
+    if threshold == 0 {
+        return Err("Threshold must be at least 1".to_string());
+    }
+    if ids.len() < threshold {
+        return Err(format!(
+            "Not enough shares: got {}, need {}",
+            ids.len(),
+            threshold
+        ));
+    }
+    check_share_ids(ids)?;
+
+    fr_lagrange_interpolation(&ids[..threshold], &shares[..threshold])
+}
+
+const SECRET_SHARE_VERSION: u8 = 1;
+const SECRET_CHUNK_SIZE: usize = 31;
+const SECRET_DIGEST_SIZE: usize = 16;
+const SECRET_SPLIT_ID_SIZE: usize = 8;
+const SECRET_CHECKSUM_SIZE: usize = 4;
+const SECRET_HEADER_SIZE: usize = 1 + 1 + SECRET_SPLIT_ID_SIZE + 1 + 4;
+
+struct SecretShare {
+    threshold: u8,
+    split_id: [u8; SECRET_SPLIT_ID_SIZE],
+    id: u8,
+    length: u32,
+    chunks: Vec<mclBnFr>,
+}
+
+fn secret_chunk_count(length: usize) -> usize {
+    (length + SECRET_DIGEST_SIZE).div_ceil(SECRET_CHUNK_SIZE)
+}
+
+fn encode_secret_share(share: &SecretShare) -> Result<String, String> {
+    let mut bytes = vec![SECRET_SHARE_VERSION, share.threshold];
+    bytes.extend_from_slice(&share.split_id);
+    bytes.push(share.id);
+    bytes.extend_from_slice(&share.length.to_be_bytes());
+
+    for chunk in &share.chunks {
+        let chunk_bytes = serialize_fr(chunk);
+        if chunk_bytes.len() != FR_SIZE {
+            return Err("Failed to serialize share chunk".to_string());
+        }
+        bytes.extend_from_slice(&chunk_bytes);
+    }
+
+    let checksum = Sha256::digest(&bytes);
+    bytes.extend_from_slice(&checksum[..SECRET_CHECKSUM_SIZE]);
+
+    Ok(bytes_to_hex(&bytes))
+}
+
+fn decode_secret_share(share_hex: &str) -> Result<SecretShare, String> {
+    let bytes = hex_to_bytes(share_hex)?;
+    if bytes.len() < SECRET_HEADER_SIZE + SECRET_CHECKSUM_SIZE {
+        return Err("Share is too short".to_string());
+    }
+
+    let (body, checksum) = bytes.split_at(bytes.len() - SECRET_CHECKSUM_SIZE);
+    if Sha256::digest(body)[..SECRET_CHECKSUM_SIZE] != *checksum {
+        return Err("Share checksum mismatch".to_string());
+    }
+    if body[0] != SECRET_SHARE_VERSION {
+        return Err(format!("Unsupported share version {}", body[0]));
+    }
+
+    let threshold = body[1];
+    let mut split_id = [0u8; SECRET_SPLIT_ID_SIZE];
+    split_id.copy_from_slice(&body[2..2 + SECRET_SPLIT_ID_SIZE]);
+    let id = body[2 + SECRET_SPLIT_ID_SIZE];
+    let length_offset = 3 + SECRET_SPLIT_ID_SIZE;
+    let mut length_bytes = [0u8; 4];
+    length_bytes.copy_from_slice(&body[length_offset..length_offset + 4]);
+    let length = u32::from_be_bytes(length_bytes);
+
+    let chunk_bytes = &body[SECRET_HEADER_SIZE..];
+    if chunk_bytes.len() != secret_chunk_count(length as usize) * FR_SIZE {
+        return Err("Share length does not match its header".to_string());
+    }
+
+    let chunks = chunk_bytes
+        .chunks(FR_SIZE)
+        .map(deserialize_fr)
+        .collect::<Result<Vec<mclBnFr>, String>>()?;
+
+    Ok(SecretShare {
+        threshold,
+        split_id,
+        id,
+        length,
+        chunks,
+    })
+}
+
+pub fn split_secret_bytes(
+    secret: &[u8],
+    threshold: usize,
+    num_shares: usize,
+) -> Result<Vec<String>, String> {
+    if threshold == 0 {
+        return Err("Threshold must be at least 1".to_string());
+    }
+    if threshold > num_shares {
+        return Err("Threshold cannot exceed number of shares".to_string());
+    }
+    if num_shares > u8::MAX as usize {
+        return Err(format!("At most {} shares are supported", u8::MAX));
+    }
+    let length = u32::try_from(secret.len()).map_err(|_| "Secret is too long".to_string())?;
+
+    let mut payload = secret.to_vec();
+    payload.extend_from_slice(&Sha256::digest(secret)[..SECRET_DIGEST_SIZE]);
+
+    let mut split_id = [0u8; SECRET_SPLIT_ID_SIZE];
+    OsRng.fill_bytes(&mut split_id);
+
+    let mut shares: Vec<SecretShare> = (1..=num_shares as u8)
+        .map(|id| SecretShare {
+            threshold: threshold as u8,
+            split_id,
+            id,
+            length,
+            chunks: Vec::new(),
+        })
+        .collect();
+
+    unsafe {
+        for chunk in payload.chunks(SECRET_CHUNK_SIZE) {
+            let mut coefficients: Vec<mclBnFr> = Vec::new();
+            let mut constant: mclBnFr = std::mem::zeroed();
+            if mclBnFr_setLittleEndian(&mut constant, chunk.as_ptr() as *const _, chunk.len()) != 0
+            {
+                return Err("Failed to convert secret chunk".to_string());
+            }
+            coefficients.push(constant);
+
+            for _ in 1..threshold {
+                let mut coefficient: mclBnFr = std::mem::zeroed();
+                if mclBnFr_setByCSPRNG(&mut coefficient) != 0 {
+                    return Err("Failed to generate random coefficient".to_string());
+                }
+                coefficients.push(coefficient);
+            }
+
+            for share in shares.iter_mut() {
+                let mut x: mclBnFr = std::mem::zeroed();
+                mclBnFr_setInt(&mut x, share.id as i64);
+                let value = fr_evaluate_polynomial(&coefficients, &x)?;
+                share.chunks.push(value);
+            }
+        }
+    }
+
+    shares.iter().map(encode_secret_share).collect()
+}
+
+pub fn combine_secret_bytes(shares: &[String]) -> Result<Vec<u8>, String> {
+    let decoded = shares
+        .iter()
+        .map(|share| decode_secret_share(share))
+        .collect::<Result<Vec<SecretShare>, String>>()?;
+
+    let first = decoded.first().ok_or("No shares provided")?;
+    let threshold = first.threshold as usize;
+
+    for share in &decoded {
+        if share.threshold != first.threshold
+            || share.split_id != first.split_id
+            || share.length != first.length
+        {
+            return Err("Shares belong to different secrets".to_string());
+        }
+    }
+    if decoded.len() < threshold {
+        return Err(format!(
+            "Not enough shares: got {}, need {}",
+            decoded.len(),
+            threshold
+        ));
+    }
+
+    let selected = &decoded[..threshold];
+    let ids: Vec<mclBnFr> = selected
+        .iter()
+        .map(|share| unsafe {
+            let mut x: mclBnFr = std::mem::zeroed();
+            mclBnFr_setInt(&mut x, share.id as i64);
+            x
+        })
+        .collect();
+    check_share_ids(&ids)?;
+
+    let mut payload: Vec<u8> = Vec::new();
+    for chunk_index in 0..first.chunks.len() {
+        let values: Vec<mclBnFr> = selected
+            .iter()
+            .map(|share| share.chunks[chunk_index])
+            .collect();
+        let chunk = fr_lagrange_interpolation(&ids, &values)?;
+
+        let mut buf = [0u8; FR_SIZE];
+        let size = unsafe { mclBnFr_getLittleEndian(buf.as_mut_ptr() as *mut _, FR_SIZE, &chunk) };
+        if size == 0 || buf[SECRET_CHUNK_SIZE..].iter().any(|b| *b != 0) {
+            return Err("Recovered chunk is out of range".to_string());
+        }
+        payload.extend_from_slice(&buf[..SECRET_CHUNK_SIZE]);
+    }
+
+    let length = first.length as usize;
+    let digest = &payload[length..length + SECRET_DIGEST_SIZE];
+    let secret = &payload[..length];
+    if Sha256::digest(secret)[..SECRET_DIGEST_SIZE] != *digest {
+        return Err("Recovered secret failed integrity check".to_string());
+    }
+
+    Ok(secret.to_vec())
+}
@@ -1,14 +1,43 @@
 use super::ffi::*;
 use crate::types::*;
+use super::secret_sharing::{fr_lagrange_coefficient, g2_evaluate_polynomial};
 use super::{
-    pvsh_encode_g2, pvsh_decode_g2,
+    pvsh_encode_g2, pvsh_decode_g2, pvsh_verify_g2,
     serialize_fr, serialize_g2, deserialize_fr, deserialize_g2,
-    derive_public_key_g2, get_g2_generator,
+    derive_public_key_g2, get_g2_generator, sign, verify, decrypt_ecies_with_shared_point,
 };
+use super::nizk::{prove_dleq, verify_dleq};
 use super::utils::{bytes_to_hex, hex_to_bytes};
+use std::collections::HashSet;
 use std::mem;
 
 pub fn generate_contribution(threshold: usize, members: &[Member]) -> Result<Contribution, String> {
+    generate_contribution_internal(threshold, members, None)
+}
+
+pub fn generate_reshare_contribution(
+    actor_share: &ActorShare,
+    new_threshold: usize,
+    new_members: &[Member],
+) -> Result<Contribution, String> {
+    let sh_bytes = hex_to_bytes(&actor_share.sh)?;
+    let sh = deserialize_fr(&sh_bytes)?;
+    generate_contribution_internal(new_threshold, new_members, Some(&sh))
+}
+
+pub fn generate_refresh_contribution(
+    threshold: usize,
+    members: &[Member],
+) -> Result<Contribution, String> {
+    let zero: mclBnFr = unsafe { mem::zeroed() };
+    generate_contribution_internal(threshold, members, Some(&zero))
+}
+
+pub(crate) fn generate_contribution_internal(
+    threshold: usize,
+    members: &[Member],
+    constant_term: Option<&mclBnFr>,
+) -> Result<Contribution, String> {
     if threshold == 0 {
         return Err("Threshold must be at least 1".to_string());
     }
@@ -25,11 +54,16 @@ pub fn generate_contribution(threshold: usize, members: &[Member]) -> Result<Con
         let mut bls_secret_coefficients: Vec<BlsSecretKey> = Vec::new();
         let mut public_generators: Vec<mclBnG2> = Vec::new();
 
-        for _ in 0..threshold {
+        for k in 0..threshold {
             let mut bls_sg: BlsSecretKey = mem::zeroed();
-            let ret = blsSecretKeySetByCSPRNG(&mut bls_sg);
-            if ret != 0 {
-                return Err("Failed to generate random secret key".to_string());
+            match constant_term {
+                Some(constant) if k == 0 => bls_sg.v = *constant,
+                _ => {
+                    let ret = blsSecretKeySetByCSPRNG(&mut bls_sg);
+                    if ret != 0 {
+                        return Err("Failed to generate random secret key".to_string());
+                    }
+                }
             }
 
             let mut bls_pg: BlsPublicKey = mem::zeroed();
@@ -103,7 +137,7 @@ pub fn generate_contribution(threshold: usize, members: &[Member]) -> Result<Con
 pub fn calculate_threshold_keys(
     actor_id: &str,
     threshold: usize,
-    _members: &[Member],
+    members: &[Member],
     contributions: &[ReceivedContribution],
 ) -> Result<ThresholdKeys, String> {
     if contributions.is_empty() {
@@ -134,6 +168,53 @@ pub fn calculate_threshold_keys(
             bls_contributor_ids.push(bls_id);
         }
 
+        let helper_g2 = get_g2_generator();
+        let mut errors: Vec<ThresholdKeyError> = Vec::new();
+
+        for contrib in contributions_to_use {
+            let pgs = contrib
+                .contribution
+                .pg
+                .iter()
+                .map(|pg_hex| deserialize_g2(&hex_to_bytes(pg_hex)?))
+                .collect::<Result<Vec<mclBnG2>, String>>()?;
+
+            for esh in &contrib.contribution.esh {
+                let receiver_id_bytes = hex_to_bytes(&esh.receiver_id)?;
+                let receiver_id = deserialize_fr(&receiver_id_bytes)?;
+                let receiver_pk_bytes = hex_to_bytes(&esh.receiver_pk)?;
+                let receiver_pk = deserialize_g2(&receiver_pk_bytes)?;
+
+                let expected_public = g2_evaluate_polynomial(&pgs, &receiver_id)?;
+                if let Err(reason) = pvsh_verify_g2(
+                    &receiver_id,
+                    &receiver_pk,
+                    &expected_public,
+                    &esh.esh,
+                    &helper_g2,
+                ) {
+                    errors.push(ThresholdKeyError {
+                        sender_id: Some(contrib.sender_id.clone()),
+                        receiver_id: esh.receiver_id.clone(),
+                        reason,
+                    });
+                }
+            }
+        }
+
+        if !errors.is_empty() {
+            return Ok(ThresholdKeys {
+                actor_id: actor_id.to_string(),
+                id: String::new(),
+                sh: String::new(),
+                ph: String::new(),
+                phs: Vec::new(),
+                pg: Vec::new(),
+                group_key: String::new(),
+                errors,
+            });
+        }
+
         let first_contrib = &contributions_to_use[0].contribution;
         let pg_count = first_contrib.pg.len();
         let mut recovered_pgs: Vec<mclBnG2> = Vec::new();
@@ -173,14 +254,107 @@ pub fn calculate_threshold_keys(
             .iter()
             .map(|pg| bytes_to_hex(&serialize_g2(pg)))
             .collect();
+        let phs = compute_public_shares(&pg_hex, members)?;
 
         Ok(ThresholdKeys {
             actor_id: actor_id.to_string(),
+            id: String::new(),
+            sh: String::new(),
+            ph: String::new(),
+            phs,
+            group_key: pg_hex[0].clone(),
             pg: pg_hex,
+            errors: Vec::new(),
         })
     }
 }
 
+pub fn threshold_key_from_actor_share(actor_share: &ActorShare, my_id: &str) -> ThresholdKeys {
+    ThresholdKeys {
+        actor_id: actor_share.actor_id.clone(),
+        id: my_id.to_string(),
+        sh: actor_share.sh.clone(),
+        ph: actor_share.ph.clone(),
+        phs: actor_share.phs.clone(),
+        group_key: actor_share.pg.first().cloned().unwrap_or_default(),
+        pg: actor_share.pg.clone(),
+        errors: Vec::new(),
+    }
+}
+
+pub fn threshold_key_to_bytes(keys: &ThresholdKeys) -> Result<ThresholdKeyBytes, String> {
+    let optional_bytes = |value: &str| -> Result<Vec<u8>, String> {
+        if value.is_empty() {
+            Ok(Vec::new())
+        } else {
+            hex_to_bytes(value)
+        }
+    };
+
+    let mut phs: Vec<(Vec<u8>, Vec<u8>)> = Vec::new();
+    for public_share in &keys.phs {
+        phs.push((
+            hex_to_bytes(&public_share.id)?,
+            hex_to_bytes(&public_share.ph)?,
+        ));
+    }
+
+    Ok(ThresholdKeyBytes {
+        actor_id: keys.actor_id.clone(),
+        id: optional_bytes(&keys.id)?,
+        sh: optional_bytes(&keys.sh)?,
+        ph: optional_bytes(&keys.ph)?,
+        phs,
+        group_key: hex_to_bytes(&keys.group_key)?,
+        pg: keys
+            .pg
+            .iter()
+            .map(|pg| hex_to_bytes(pg))
+            .collect::<Result<Vec<Vec<u8>>, String>>()?,
+        errors: keys.errors.clone(),
+    })
+}
+
+pub fn threshold_key_from_bytes(bytes: &ThresholdKeyBytes) -> Result<ThresholdKeys, String> {
+    let fr_hex = |value: &[u8]| -> Result<String, String> {
+        if value.is_empty() {
+            Ok(String::new())
+        } else {
+            Ok(bytes_to_hex(&serialize_fr(&deserialize_fr(value)?)))
+        }
+    };
+    let g2_hex = |value: &[u8]| -> Result<String, String> {
+        if value.is_empty() {
+            Ok(String::new())
+        } else {
+            Ok(bytes_to_hex(&serialize_g2(&deserialize_g2(value)?)))
+        }
+    };
+
+    let mut phs: Vec<PublicShare> = Vec::new();
+    for (id, ph) in &bytes.phs {
+        phs.push(PublicShare {
+            id: fr_hex(id)?,
+            ph: g2_hex(ph)?,
+        });
+    }
+
+    Ok(ThresholdKeys {
+        actor_id: bytes.actor_id.clone(),
+        id: fr_hex(&bytes.id)?,
+        sh: fr_hex(&bytes.sh)?,
+        ph: g2_hex(&bytes.ph)?,
+        phs,
+        group_key: g2_hex(&bytes.group_key)?,
+        pg: bytes
+            .pg
+            .iter()
+            .map(|pg| g2_hex(pg))
+            .collect::<Result<Vec<String>, String>>()?,
+        errors: bytes.errors.clone(),
+    })
+}
+
 pub fn generate_actor_share(
     actor_id: &str,
     actor_contract: &ActorContract,
@@ -210,8 +384,7 @@ pub fn generate_actor_share(
             public_share: mclBnG2,
         }
 
-        let mut participants: std::collections::HashMap<String, ParticipantData> =
-            std::collections::HashMap::new();
+        let mut participants: Vec<(String, ParticipantData)> = Vec::new();
 
         let _helper_g2 = get_g2_generator();
 
@@ -282,14 +455,14 @@ pub fn generate_actor_share(
                 let decrypted_share =
                     pvsh_decode_g2(&receiver_id, &receiver_pk, &my_sk_fr, &my_esh.esh)?;
 
-                participants.insert(
+                participants.push((
                     contribution.sender_id.clone(),
                     ParticipantData {
                         sender_id,
                         decrypted_share,
                         public_share,
                     },
-                );
+                ));
             }
         }
 
@@ -301,7 +474,7 @@ pub fn generate_actor_share(
             ));
         }
 
-        let participant_list: Vec<_> = participants.values().collect();
+        let participant_list: Vec<_> = participants.iter().map(|(_, p)| p).collect();
 
         let mut bls_ids: Vec<BlsId> = Vec::new();
         for p in &participant_list {
@@ -405,20 +578,11 @@ pub fn generate_actor_share(
             recovered_pgs.push(recovered_pg_bls.v);
         }
 
-        let mut all_phs: Vec<PublicShare> = Vec::new();
-
-        for (sender_id_str, participant) in &participants {
-            let ph_hex = bytes_to_hex(&serialize_g2(&participant.public_share));
-            all_phs.push(PublicShare {
-                id: sender_id_str.clone(),
-                ph: ph_hex,
-            });
-        }
-
         let pg_hex: Vec<String> = recovered_pgs
             .iter()
             .map(|pg| bytes_to_hex(&serialize_g2(pg)))
             .collect();
+        let all_phs = compute_public_shares(&pg_hex, &actor_contract.new_members)?;
 
         Ok(ActorShare {
             actor_id: actor_id.to_string(),
@@ -432,6 +596,962 @@ pub fn generate_actor_share(
             sh: bytes_to_hex(&serialize_fr(&my_recovered_secret)),
             ph: bytes_to_hex(&serialize_g2(&my_recovered_public)),
             phs: all_phs,
+            epoch: 0,
+            member_id: my_id.to_string(),
+            recovery_only: is_guardian(actor_contract, my_id),
+        })
+    }
+}
+
+pub fn refresh_actor_share(
+    actor_share: &ActorShare,
+    refresh_contract: &ActorContract,
+    my_id: &str,
+    my_secret_key: &str,
+) -> Result<ActorShare, String> {
+    let threshold = refresh_contract.threshold;
+    let contributions = &refresh_contract.contributions;
+
+    if threshold != actor_share.pg.len() {
+        return Err(format!(
+            "Refresh threshold mismatch: got {}, share uses {}",
+            threshold,
+            actor_share.pg.len()
+        ));
+    }
+    if contributions.len() < threshold {
+        return Err(format!(
+            "Not enough contributions: got {}, need {}",
+            contributions.len(),
+            threshold
+        ));
+    }
+
+    unsafe {
+        let helper_g2 = get_g2_generator();
+
+        let my_id_bytes = hex_to_bytes(my_id)?;
+        let my_id_fr = deserialize_fr(&my_id_bytes)?;
+
+        let my_sk_bytes = hex_to_bytes(my_secret_key)?;
+        let my_sk_fr = deserialize_fr(&my_sk_bytes)?;
+        let my_pk = derive_public_key_g2(&my_sk_fr);
+
+        let sh_bytes = hex_to_bytes(&actor_share.sh)?;
+        let mut sh = deserialize_fr(&sh_bytes)?;
+
+        let mut pgs: Vec<mclBnG2> = Vec::new();
+        for pg_hex in &actor_share.pg {
+            let pg_bytes = hex_to_bytes(pg_hex)?;
+            pgs.push(deserialize_g2(&pg_bytes)?);
+        }
+
+        let mut senders = HashSet::new();
+        for contribution in contributions {
+            let sender_id = deserialize_fr(&hex_to_bytes(&contribution.sender_id)?)?;
+            if !senders.insert(serialize_fr(&sender_id)) {
+                return Err(format!(
+                    "Duplicate refresh contribution from {}",
+                    contribution.sender_id
+                ));
+            }
+
+            let delta_pgs = contribution
+                .contribution
+                .pg
+                .iter()
+                .map(|pg_hex| deserialize_g2(&hex_to_bytes(pg_hex)?))
+                .collect::<Result<Vec<mclBnG2>, String>>()?;
+
+            if delta_pgs.len() != threshold {
+                return Err("Inconsistent PG lengths in refresh contribution".to_string());
+            }
+            if mclBnG2_isZero(&delta_pgs[0]) == 0 {
+                return Err(format!(
+                    "Refresh contribution from {} does not share zero",
+                    contribution.sender_id
+                ));
+            }
+
+            let my_esh = contribution
+                .contribution
+                .esh
+                .iter()
+                .find(|esh| esh.receiver_id == my_id)
+                .ok_or_else(|| {
+                    format!(
+                        "Refresh contribution from {} has no share for me",
+                        contribution.sender_id
+                    )
+                })?;
+
+            let delta = pvsh_decode_g2(&my_id_fr, &my_pk, &my_sk_fr, &my_esh.esh)?;
+
+            let expected_public = g2_evaluate_polynomial(&delta_pgs, &my_id_fr)?;
+            let mut actual_public: mclBnG2 = mem::zeroed();
+            mclBnG2_mul(&mut actual_public, &helper_g2, &delta);
+            if mclBnG2_isEqual(&expected_public, &actual_public) == 0 {
+                return Err(format!(
+                    "Refresh share from {} does not match its commitments",
+                    contribution.sender_id
+                ));
+            }
+
+            let mut new_sh: mclBnFr = mem::zeroed();
+            mclBnFr_add(&mut new_sh, &sh, &delta);
+            sh = new_sh;
+
+            for (pg, delta_pg) in pgs.iter_mut().zip(&delta_pgs) {
+                let mut new_pg: mclBnG2 = mem::zeroed();
+                mclBnG2_add(&mut new_pg, pg, delta_pg);
+                *pg = new_pg;
+            }
+        }
+
+        let ph = derive_public_key_g2(&sh);
+        let expected_ph = g2_evaluate_polynomial(&pgs, &my_id_fr)?;
+        if mclBnG2_isEqual(&ph, &expected_ph) == 0 {
+            return Err("Refreshed share does not match refreshed PG".to_string());
+        }
+
+        let pg_hex: Vec<String> = pgs
+            .iter()
+            .map(|pg| bytes_to_hex(&serialize_g2(pg)))
+            .collect();
+        let phs = compute_public_shares(&pg_hex, &refresh_contract.new_members)?;
+
+        Ok(ActorShare {
+            pg: pg_hex,
+            sh: bytes_to_hex(&serialize_fr(&sh)),
+            ph: bytes_to_hex(&serialize_g2(&ph)),
+            phs,
+            epoch: actor_share.epoch + 1,
+            ..actor_share.clone()
+        })
+    }
+}
+
+pub fn compute_public_shares(
+    pg: &[String],
+    members: &[Member],
+) -> Result<Vec<PublicShare>, String> {
+    let pgs = pg
+        .iter()
+        .map(|pg_hex| deserialize_g2(&hex_to_bytes(pg_hex)?))
+        .collect::<Result<Vec<mclBnG2>, String>>()?;
+
+    let mut phs: Vec<PublicShare> = Vec::new();
+    for member in members {
+        let member_id_bytes = hex_to_bytes(&member.id)?;
+        let member_id = deserialize_fr(&member_id_bytes)?;
+        let member_ph = g2_evaluate_polynomial(&pgs, &member_id)?;
+        phs.push(PublicShare {
+            id: member.id.clone(),
+            ph: bytes_to_hex(&serialize_g2(&member_ph)),
+        });
+    }
+
+    Ok(phs)
+}
+
+pub fn verify_reshare_contribution(
+    reshare: &ReceivedContribution,
+    old_public_shares: &[PublicShare],
+    new_threshold: usize,
+) -> Result<(), String> {
+    let sender_ph = old_public_shares
+        .iter()
+        .find(|ph| ph.id == reshare.sender_id)
+        .ok_or_else(|| format!("No public share for resharing member {}", reshare.sender_id))?;
+
+    if reshare.contribution.pg.len() != new_threshold {
+        return Err(format!(
+            "Reshare from {} has {} PGs, expected {}",
+            reshare.sender_id,
+            reshare.contribution.pg.len(),
+            new_threshold
+        ));
+    }
+
+    unsafe {
+        let helper_g2 = get_g2_generator();
+
+        let pgs = reshare
+            .contribution
+            .pg
+            .iter()
+            .map(|pg_hex| deserialize_g2(&hex_to_bytes(pg_hex)?))
+            .collect::<Result<Vec<mclBnG2>, String>>()?;
+
+        let ph_bytes = hex_to_bytes(&sender_ph.ph)?;
+        let ph = deserialize_g2(&ph_bytes)?;
+        if mclBnG2_isEqual(&pgs[0], &ph) == 0 {
+            return Err(format!(
+                "Reshare from {} does not commit to its public share",
+                reshare.sender_id
+            ));
+        }
+
+        for esh in &reshare.contribution.esh {
+            let receiver_id_bytes = hex_to_bytes(&esh.receiver_id)?;
+            let receiver_id = deserialize_fr(&receiver_id_bytes)?;
+            let receiver_pk_bytes = hex_to_bytes(&esh.receiver_pk)?;
+            let receiver_pk = deserialize_g2(&receiver_pk_bytes)?;
+
+            let expected_public = g2_evaluate_polynomial(&pgs, &receiver_id)?;
+            pvsh_verify_g2(
+                &receiver_id,
+                &receiver_pk,
+                &expected_public,
+                &esh.esh,
+                &helper_g2,
+            )
+            .map_err(|e| {
+                format!(
+                    "Reshare from {} to {}: {}",
+                    reshare.sender_id, esh.receiver_id, e
+                )
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Share of an actor reshared to the members of `reshare_contract`. The
+/// reshare deals a fresh polynomial to a new member set, so the share starts
+/// over at epoch 0; `epoch` only counts refreshes within one sharing, and new
+/// members have no previous share to take it from.
+pub fn generate_reshared_actor_share(
+    actor_id: &str,
+    old_threshold: usize,
+    old_public_shares: &[PublicShare],
+    reshare_contract: &ActorContract,
+    my_id: &str,
+    my_secret_key: &str,
+) -> Result<ActorShare, String> {
+    let new_threshold = reshare_contract.threshold;
+    let reshares = &reshare_contract.contributions;
+
+    if old_threshold == 0 || new_threshold == 0 {
+        return Err("Threshold must be at least 1".to_string());
+    }
+    if reshares.len() < old_threshold {
+        return Err(format!(
+            "Not enough reshares: got {}, need {}",
+            reshares.len(),
+            old_threshold
+        ));
+    }
+
+    let reshares = &reshares[..old_threshold];
+
+    unsafe {
+        let my_id_bytes = hex_to_bytes(my_id)?;
+        let my_id_fr = deserialize_fr(&my_id_bytes)?;
+
+        let my_sk_bytes = hex_to_bytes(my_secret_key)?;
+        let my_sk_fr = deserialize_fr(&my_sk_bytes)?;
+        let my_pk = derive_public_key_g2(&my_sk_fr);
+
+        let mut bls_ids: Vec<BlsId> = Vec::new();
+        let mut bls_secret_shares: Vec<BlsSecretKey> = Vec::new();
+
+        for reshare in reshares {
+            verify_reshare_contribution(reshare, old_public_shares, new_threshold)?;
+
+            let sender_id_bytes = hex_to_bytes(&reshare.sender_id)?;
+            let sender_id = deserialize_fr(&sender_id_bytes)?;
+            if bls_ids
+                .iter()
+                .any(|id: &BlsId| mclBnFr_isEqual(&id.v, &sender_id) != 0)
+            {
+                return Err(format!("Duplicate reshare from {}", reshare.sender_id));
+            }
+
+            let my_esh = reshare
+                .contribution
+                .esh
+                .iter()
+                .find(|esh| esh.receiver_id == my_id)
+                .ok_or_else(|| format!("Reshare from {} has no share for me", reshare.sender_id))?;
+
+            let share = pvsh_decode_g2(&my_id_fr, &my_pk, &my_sk_fr, &my_esh.esh)?;
+
+            bls_ids.push(BlsId { v: sender_id });
+            bls_secret_shares.push(BlsSecretKey { v: share });
+        }
+
+        let mut my_secret_bls: BlsSecretKey = mem::zeroed();
+        let ret = blsSecretKeyRecover(
+            &mut my_secret_bls,
+            bls_secret_shares.as_ptr(),
+            bls_ids.as_ptr(),
+            bls_ids.len(),
+        );
+        if ret != 0 {
+            return Err("blsSecretKeyRecover failed".to_string());
+        }
+        let sh = my_secret_bls.v;
+
+        let mut recovered_pgs: Vec<mclBnG2> = Vec::new();
+        for pg_index in 0..new_threshold {
+            let mut bls_pgs_at_index: Vec<BlsPublicKey> = Vec::new();
+            for reshare in reshares {
+                let pg_bytes = hex_to_bytes(&reshare.contribution.pg[pg_index])?;
+                bls_pgs_at_index.push(BlsPublicKey {
+                    v: deserialize_g2(&pg_bytes)?,
+                });
+            }
+
+            let mut recovered_pg_bls: BlsPublicKey = mem::zeroed();
+            let ret = blsPublicKeyRecover(
+                &mut recovered_pg_bls,
+                bls_pgs_at_index.as_ptr(),
+                bls_ids.as_ptr(),
+                bls_ids.len(),
+            );
+            if ret != 0 {
+                return Err("blsPublicKeyRecover failed for PG".to_string());
+            }
+            recovered_pgs.push(recovered_pg_bls.v);
+        }
+
+        let ph = derive_public_key_g2(&sh);
+        let expected_ph = g2_evaluate_polynomial(&recovered_pgs, &my_id_fr)?;
+        if mclBnG2_isEqual(&ph, &expected_ph) == 0 {
+            return Err("Reshared share does not match recovered PG".to_string());
+        }
+
+        let pg_hex: Vec<String> = recovered_pgs
+            .iter()
+            .map(|pg| bytes_to_hex(&serialize_g2(pg)))
+            .collect();
+        let phs = compute_public_shares(&pg_hex, &reshare_contract.new_members)?;
+
+        Ok(ActorShare {
+            actor_id: actor_id.to_string(),
+            share_code: reshare_contract.actor_share.share_code.clone(),
+            subject_actor_id: reshare_contract.actor_share.subject_actor_id.clone(),
+            hat_id: reshare_contract.actor_share.hat_id.clone(),
+            from_actor_id: reshare_contract.actor_share.from_actor_id.clone(),
+            to_actor_id: reshare_contract.actor_share.to_actor_id.clone(),
+            owner_actor_id: reshare_contract.actor_share.owner_actor_id.clone(),
+            pg: pg_hex,
+            sh: bytes_to_hex(&serialize_fr(&sh)),
+            ph: bytes_to_hex(&serialize_g2(&ph)),
+            phs,
+            epoch: 0,
+            member_id: my_id.to_string(),
+            recovery_only: is_guardian(reshare_contract, my_id),
+        })
+    }
+}
+
+fn is_guardian(contract: &ActorContract, my_id: &str) -> bool {
+    contract
+        .new_members
+        .iter()
+        .any(|member| member.id == my_id && member.role == MemberRole::Guardian)
+}
+
+/// Guardian side of social recovery: reshares a recovery-only share to the
+/// members of a recovery contract (typically the new device plus the guardians).
+pub fn generate_recovery_contribution(
+    guardian_share: &ActorShare,
+    new_threshold: usize,
+    new_members: &[Member],
+) -> Result<Contribution, String> {
+    if !guardian_share.recovery_only {
+        return Err("Only guardian shares can contribute to recovery".to_string());
+    }
+    generate_reshare_contribution(guardian_share, new_threshold, new_members)
+}
+
+/// New device side of social recovery. Every reshare in the recovery contract
+/// must come from one of `guardian_public_shares`, and the recovered share must
+/// still belong to the actor's `group_key` (its `pg[0]`).
+pub fn generate_recovered_actor_share(
+    actor_id: &str,
+    old_threshold: usize,
+    guardian_public_shares: &[PublicShare],
+    group_key: &str,
+    recovery_contract: &ActorContract,
+    my_id: &str,
+    my_secret_key: &str,
+) -> Result<ActorShare, String> {
+    let share = generate_reshared_actor_share(
+        actor_id,
+        old_threshold,
+        guardian_public_shares,
+        recovery_contract,
+        my_id,
+        my_secret_key,
+    )?;
+
+    if share.pg.first().map(String::as_str) != Some(group_key) {
+        return Err("Recovered share does not match the actor's group key".to_string());
+    }
+    Ok(share)
+}
+
+impl ActorShare {
+    /// `member_id`, or for shares stored without one, the id of the `phs`
+    /// entry holding this share's `ph`.
+    pub fn resolved_member_id(&self) -> Result<&str, String> {
+        if !self.member_id.is_empty() {
+            return Ok(&self.member_id);
+        }
+        self.phs
+            .iter()
+            .find(|public_share| public_share.ph.eq_ignore_ascii_case(&self.ph))
+            .map(|public_share| public_share.id.as_str())
+            .ok_or_else(|| "Actor share has no member id".to_string())
+    }
+
+    pub fn partial_sign(&self, data: &[u8]) -> Result<PartialSignature, String> {
+        let member_id = self.resolved_member_id()?;
+        if self.recovery_only {
+            return Err("Recovery-only shares cannot sign".to_string());
+        }
+
+        Ok(PartialSignature {
+            id: member_id.to_string(),
+            sig: sign(data, &self.sh)?,
+        })
+    }
+}
+
+pub fn verify_partial(
+    partial: &PartialSignature,
+    public_share: &PublicShare,
+    data: &[u8],
+) -> Result<(), String> {
+    if partial.id != public_share.id {
+        return Err("Partial signature id does not match public share id".to_string());
+    }
+
+    if !verify(data, &partial.sig, &public_share.ph)? {
+        return Err("INVALID_PARTIAL_SIGNATURE".to_string());
+    }
+
+    Ok(())
+}
+
+/// Interpolates the group signature from at least `threshold` partials;
+/// fewer would yield a signature that does not verify under the group key.
+pub fn combine_partial_signatures(
+    threshold: usize,
+    partials: &[PartialSignature],
+) -> Result<String, String> {
+    if threshold == 0 {
+        return Err("Threshold must be at least 1".to_string());
+    }
+    if partials.len() < threshold {
+        return Err(format!(
+            "Not enough partial signatures: got {}, need {}",
+            partials.len(),
+            threshold
+        ));
+    }
+
+    unsafe {
+        let mut bls_ids: Vec<BlsId> = Vec::new();
+        let mut bls_sigs: Vec<BlsSignature> = Vec::new();
+
+        for partial in partials {
+            let id_bytes = hex_to_bytes(&partial.id)?;
+            let id = deserialize_fr(&id_bytes)?;
+            if bls_ids.iter().any(|bls_id| mclBnFr_isEqual(&bls_id.v, &id) != 0) {
+                return Err(format!("Duplicate partial signature from {}", partial.id));
+            }
+
+            let sig_bytes = hex_to_bytes(&partial.sig)?;
+            let mut sig: BlsSignature = mem::zeroed();
+            let consumed =
+                blsSignatureDeserialize(&mut sig, sig_bytes.as_ptr() as *const _, sig_bytes.len());
+            if consumed == 0 {
+                return Err("Failed to deserialize partial signature".to_string());
+            }
+
+            bls_ids.push(BlsId { v: id });
+            bls_sigs.push(sig);
+        }
+
+        let mut combined: BlsSignature = mem::zeroed();
+        let ret = blsSignatureRecover(
+            &mut combined,
+            bls_sigs.as_ptr(),
+            bls_ids.as_ptr(),
+            bls_ids.len(),
+        );
+        if ret != 0 {
+            return Err("blsSignatureRecover failed".to_string());
+        }
+
+        let mut sig_buf = vec![0u8; BLS_SIGNATURE_SIZE];
+        let sig_size =
+            blsSignatureSerialize(sig_buf.as_mut_ptr() as *mut _, BLS_SIGNATURE_SIZE, &combined);
+        if sig_size == 0 {
+            return Err("Failed to serialize signature".to_string());
+        }
+
+        Ok(bytes_to_hex(&sig_buf[..sig_size]))
+    }
+}
+
+fn repair_weight(
+    helpers: &[Member],
+    sender_id: &str,
+    lost_id: &mclBnFr,
+) -> Result<mclBnFr, String> {
+    let helper_ids = helpers
+        .iter()
+        .map(|helper| deserialize_fr(&hex_to_bytes(&helper.id)?))
+        .collect::<Result<Vec<mclBnFr>, String>>()?;
+
+    unsafe {
+        if helper_ids
+            .iter()
+            .any(|id| mclBnFr_isEqual(id, lost_id) != 0)
+        {
+            return Err("Lost member cannot be one of the helpers".to_string());
+        }
+    }
+
+    let index = helpers
+        .iter()
+        .position(|helper| helper.id == sender_id)
+        .ok_or_else(|| format!("{} is not one of the repair helpers", sender_id))?;
+
+    fr_lagrange_coefficient(&helper_ids, index, lost_id)
+}
+
+pub fn generate_repair_contribution(
+    actor_share: &ActorShare,
+    helpers: &[Member],
+    lost_id: &str,
+) -> Result<RepairContribution, String> {
+    if helpers.len() < actor_share.pg.len() {
+        return Err(format!(
+            "Not enough repair helpers: got {}, need {}",
+            helpers.len(),
+            actor_share.pg.len()
+        ));
+    }
+
+    let member_id = actor_share.resolved_member_id()?;
+
+    unsafe {
+        let helper_g2 = get_g2_generator();
+
+        let lost_id_bytes = hex_to_bytes(lost_id)?;
+        let lost_id_fr = deserialize_fr(&lost_id_bytes)?;
+        let weight = repair_weight(helpers, member_id, &lost_id_fr)?;
+
+        let sh_bytes = hex_to_bytes(&actor_share.sh)?;
+        let sh = deserialize_fr(&sh_bytes)?;
+
+        let mut remaining: mclBnFr = mem::zeroed();
+        mclBnFr_mul(&mut remaining, &weight, &sh);
+
+        let mut parts: Vec<RepairPart> = Vec::new();
+        for (index, helper) in helpers.iter().enumerate() {
+            let part = if index == helpers.len() - 1 {
+                remaining
+            } else {
+                let mut part: mclBnFr = mem::zeroed();
+                let ret = mclBnFr_setByCSPRNG(&mut part);
+                if ret != 0 {
+                    return Err("Failed to generate random repair part".to_string());
+                }
+                let mut rest: mclBnFr = mem::zeroed();
+                mclBnFr_sub(&mut rest, &remaining, &part);
+                remaining = rest;
+                part
+            };
+
+            let helper_id_bytes = hex_to_bytes(&helper.id)?;
+            let helper_id = deserialize_fr(&helper_id_bytes)?;
+            let helper_pk_bytes = hex_to_bytes(&helper.pm)?;
+            let helper_pk = deserialize_g2(&helper_pk_bytes)?;
+
+            let esh = pvsh_encode_g2(&helper_id, &helper_pk, &part, &helper_g2)?;
+
+            parts.push(RepairPart {
+                receiver_id: helper.id.clone(),
+                receiver_pk: helper.pm.clone(),
+                ph: bytes_to_hex(&serialize_g2(&derive_public_key_g2(&part))),
+                esh,
+            });
+        }
+
+        Ok(RepairContribution {
+            sender_id: member_id.to_string(),
+            lost_id: lost_id.to_string(),
+            parts,
+        })
+    }
+}
+
+pub fn verify_repair_contribution(
+    contribution: &RepairContribution,
+    helpers: &[Member],
+    public_shares: &[PublicShare],
+) -> Result<(), String> {
+    let sender_ph = public_shares
+        .iter()
+        .find(|ph| ph.id == contribution.sender_id)
+        .ok_or_else(|| {
+            format!(
+                "No public share for repair helper {}",
+                contribution.sender_id
+            )
+        })?;
+
+    if contribution.parts.len() != helpers.len() {
+        return Err(format!(
+            "Repair contribution from {} has {} parts, expected {}",
+            contribution.sender_id,
+            contribution.parts.len(),
+            helpers.len()
+        ));
+    }
+
+    unsafe {
+        let helper_g2 = get_g2_generator();
+
+        let lost_id_bytes = hex_to_bytes(&contribution.lost_id)?;
+        let lost_id = deserialize_fr(&lost_id_bytes)?;
+        let weight = repair_weight(helpers, &contribution.sender_id, &lost_id)?;
+
+        let sender_ph_bytes = hex_to_bytes(&sender_ph.ph)?;
+        let sender_public = deserialize_g2(&sender_ph_bytes)?;
+        let mut expected_total: mclBnG2 = mem::zeroed();
+        mclBnG2_mul(&mut expected_total, &sender_public, &weight);
+
+        let mut total: mclBnG2 = mem::zeroed();
+        for (part, helper) in contribution.parts.iter().zip(helpers) {
+            if part.receiver_id != helper.id || part.receiver_pk != helper.pm {
+                return Err(format!(
+                    "Repair part from {} is not addressed to helper {}",
+                    contribution.sender_id, helper.id
+                ));
+            }
+
+            let receiver_id_bytes = hex_to_bytes(&part.receiver_id)?;
+            let receiver_id = deserialize_fr(&receiver_id_bytes)?;
+            let receiver_pk_bytes = hex_to_bytes(&part.receiver_pk)?;
+            let receiver_pk = deserialize_g2(&receiver_pk_bytes)?;
+            let part_ph_bytes = hex_to_bytes(&part.ph)?;
+            let part_public = deserialize_g2(&part_ph_bytes)?;
+
+            pvsh_verify_g2(
+                &receiver_id,
+                &receiver_pk,
+                &part_public,
+                &part.esh,
+                &helper_g2,
+            )?;
+
+            let mut sum: mclBnG2 = mem::zeroed();
+            mclBnG2_add(&mut sum, &total, &part_public);
+            total = sum;
+        }
+
+        if mclBnG2_isEqual(&total, &expected_total) == 0 {
+            return Err(format!(
+                "Repair parts from {} do not add up to its weighted public share",
+                contribution.sender_id
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn generate_repair_share(
+    actor_share: &ActorShare,
+    my_secret_key: &str,
+    helpers: &[Member],
+    contributions: &[RepairContribution],
+    replacement: &Member,
+) -> Result<RepairShare, String> {
+    if contributions.len() != helpers.len() {
+        return Err(format!(
+            "Expected one repair contribution per helper: got {}, need {}",
+            contributions.len(),
+            helpers.len()
+        ));
+    }
+
+    let my_id = actor_share.resolved_member_id()?;
+    let public_shares = compute_public_shares(&actor_share.pg, helpers)?;
+
+    unsafe {
+        let helper_g2 = get_g2_generator();
+
+        let my_id_bytes = hex_to_bytes(my_id)?;
+        let my_id_fr = deserialize_fr(&my_id_bytes)?;
+        let my_sk_bytes = hex_to_bytes(my_secret_key)?;
+        let my_sk_fr = deserialize_fr(&my_sk_bytes)?;
+        let my_pk = derive_public_key_g2(&my_sk_fr);
+
+        let mut sigma: mclBnFr = mem::zeroed();
+        let mut sigma_public: mclBnG2 = mem::zeroed();
+
+        for helper in helpers {
+            let contribution = contributions
+                .iter()
+                .find(|contribution| contribution.sender_id == helper.id)
+                .ok_or_else(|| format!("Missing repair contribution from {}", helper.id))?;
+
+            if contribution.lost_id != replacement.id {
+                return Err(format!(
+                    "Repair contribution from {} targets a different member",
+                    contribution.sender_id
+                ));
+            }
+
+            verify_repair_contribution(contribution, helpers, &public_shares)?;
+
+            let my_part = contribution
+                .parts
+                .iter()
+                .find(|part| part.receiver_id == my_id)
+                .ok_or_else(|| {
+                    format!(
+                        "Repair contribution from {} has no part for me",
+                        contribution.sender_id
+                    )
+                })?;
+
+            let part = pvsh_decode_g2(&my_id_fr, &my_pk, &my_sk_fr, &my_part.esh)?;
+            let part_ph_bytes = hex_to_bytes(&my_part.ph)?;
+            let part_public = deserialize_g2(&part_ph_bytes)?;
+
+            let mut sum: mclBnFr = mem::zeroed();
+            mclBnFr_add(&mut sum, &sigma, &part);
+            sigma = sum;
+
+            let mut public_sum: mclBnG2 = mem::zeroed();
+            mclBnG2_add(&mut public_sum, &sigma_public, &part_public);
+            sigma_public = public_sum;
+        }
+
+        let replacement_id_bytes = hex_to_bytes(&replacement.id)?;
+        let replacement_id = deserialize_fr(&replacement_id_bytes)?;
+        let replacement_pk_bytes = hex_to_bytes(&replacement.pm)?;
+        let replacement_pk = deserialize_g2(&replacement_pk_bytes)?;
+
+        let esh = pvsh_encode_g2(&replacement_id, &replacement_pk, &sigma, &helper_g2)?;
+
+        Ok(RepairShare {
+            sender_id: my_id.to_string(),
+            lost_id: replacement.id.clone(),
+            ph: bytes_to_hex(&serialize_g2(&sigma_public)),
+            esh,
+            epoch: actor_share.epoch,
         })
     }
 }
+
+pub fn recover_repaired_actor_share(
+    keys: &ThresholdKeys,
+    actor_share_data: &ActorShareData,
+    repair_shares: &[RepairShare],
+    my_id: &str,
+    my_secret_key: &str,
+) -> Result<ActorShare, String> {
+    if repair_shares.is_empty() {
+        return Err("No repair shares provided".to_string());
+    }
+    let epoch = repair_shares[0].epoch;
+    if repair_shares.iter().any(|share| share.epoch != epoch) {
+        return Err("Repair shares come from different epochs".to_string());
+    }
+
+    unsafe {
+        let helper_g2 = get_g2_generator();
+
+        let my_id_bytes = hex_to_bytes(my_id)?;
+        let my_id_fr = deserialize_fr(&my_id_bytes)?;
+        let my_sk_bytes = hex_to_bytes(my_secret_key)?;
+        let my_sk_fr = deserialize_fr(&my_sk_bytes)?;
+        let my_pk = derive_public_key_g2(&my_sk_fr);
+
+        let mut sh: mclBnFr = mem::zeroed();
+        for repair_share in repair_shares {
+            if repair_share.lost_id != my_id {
+                return Err(format!(
+                    "Repair share from {} is not addressed to me",
+                    repair_share.sender_id
+                ));
+            }
+
+            let ph_bytes = hex_to_bytes(&repair_share.ph)?;
+            let public = deserialize_g2(&ph_bytes)?;
+            pvsh_verify_g2(&my_id_fr, &my_pk, &public, &repair_share.esh, &helper_g2)?;
+
+            let sigma = pvsh_decode_g2(&my_id_fr, &my_pk, &my_sk_fr, &repair_share.esh)?;
+            let mut sum: mclBnFr = mem::zeroed();
+            mclBnFr_add(&mut sum, &sh, &sigma);
+            sh = sum;
+        }
+
+        let pgs = keys
+            .pg
+            .iter()
+            .map(|pg_hex| deserialize_g2(&hex_to_bytes(pg_hex)?))
+            .collect::<Result<Vec<mclBnG2>, String>>()?;
+
+        let ph = derive_public_key_g2(&sh);
+        let expected_ph = g2_evaluate_polynomial(&pgs, &my_id_fr)?;
+        if mclBnG2_isEqual(&ph, &expected_ph) == 0 {
+            return Err("Repaired share does not match the actor's PG".to_string());
+        }
+
+        Ok(ActorShare {
+            actor_id: keys.actor_id.clone(),
+            share_code: actor_share_data.share_code.clone(),
+            subject_actor_id: actor_share_data.subject_actor_id.clone(),
+            hat_id: actor_share_data.hat_id.clone(),
+            from_actor_id: actor_share_data.from_actor_id.clone(),
+            to_actor_id: actor_share_data.to_actor_id.clone(),
+            owner_actor_id: actor_share_data.owner_actor_id.clone(),
+            pg: keys.pg.clone(),
+            sh: bytes_to_hex(&serialize_fr(&sh)),
+            ph: bytes_to_hex(&serialize_g2(&ph)),
+            phs: keys.phs.clone(),
+            epoch,
+            member_id: my_id.to_string(),
+            recovery_only: false,
+        })
+    }
+}
+
+const DECRYPTION_SHARE_DST: &[u8] = b"SECURITY-RS-THRESHOLD-DECRYPT-DLEQ-V1";
+
+pub fn generate_decryption_share(
+    actor_share: &ActorShare,
+    encrypted: &EncryptedData,
+) -> Result<DecryptionShare, String> {
+    let member_id = actor_share.resolved_member_id()?;
+    if actor_share.recovery_only {
+        return Err("Recovery-only shares cannot decrypt".to_string());
+    }
+
+    unsafe {
+        let sh_bytes = hex_to_bytes(&actor_share.sh)?;
+        let sh = deserialize_fr(&sh_bytes)?;
+
+        let ephemeral_bytes = hex_to_bytes(&encrypted.ephemeral_public_key)?;
+        let ephemeral_pk = deserialize_g2(&ephemeral_bytes)?;
+
+        let mut u: mclBnG2 = mem::zeroed();
+        mclBnG2_mul(&mut u, &ephemeral_pk, &sh);
+
+        let g2 = get_g2_generator();
+        let proof = prove_dleq(DECRYPTION_SHARE_DST, &g2, &ephemeral_pk, &sh)?;
+
+        Ok(DecryptionShare {
+            id: member_id.to_string(),
+            u: bytes_to_hex(&serialize_g2(&u)),
+            proof,
+        })
+    }
+}
+
+pub fn verify_decryption_share(
+    share: &DecryptionShare,
+    public_share: &PublicShare,
+    encrypted: &EncryptedData,
+) -> Result<(), String> {
+    if share.id != public_share.id {
+        return Err("Decryption share id does not match public share id".to_string());
+    }
+
+    let u = deserialize_g2(&hex_to_bytes(&share.u)?)?;
+    let ph = deserialize_g2(&hex_to_bytes(&public_share.ph)?)?;
+    let ephemeral_pk = deserialize_g2(&hex_to_bytes(&encrypted.ephemeral_public_key)?)?;
+
+    verify_dleq(
+        DECRYPTION_SHARE_DST,
+        &get_g2_generator(),
+        &ph,
+        &ephemeral_pk,
+        &u,
+        &share.proof,
+    )
+    .map_err(|_| "INVALID_DECRYPTION_SHARE".to_string())
+}
+
+pub fn combine_decryption_shares(
+    encrypted: &EncryptedData,
+    threshold: usize,
+    shares: &[DecryptionShare],
+    public_shares: &[PublicShare],
+) -> Result<Vec<u8>, String> {
+    if threshold == 0 {
+        return Err("Threshold must be at least 1".to_string());
+    }
+
+    unsafe {
+        let mut bls_ids: Vec<BlsId> = Vec::new();
+        let mut bls_us: Vec<BlsPublicKey> = Vec::new();
+        let mut rejected: Vec<String> = Vec::new();
+
+        for share in shares {
+            if bls_ids.len() == threshold {
+                break;
+            }
+
+            let public_share = match public_shares.iter().find(|ph| ph.id == share.id) {
+                Some(public_share) => public_share,
+                None => {
+                    rejected.push(share.id.clone());
+                    continue;
+                }
+            };
+            if verify_decryption_share(share, public_share, encrypted).is_err() {
+                rejected.push(share.id.clone());
+                continue;
+            }
+
+            let id = deserialize_fr(&hex_to_bytes(&share.id)?)?;
+            if bls_ids
+                .iter()
+                .any(|bls_id| mclBnFr_isEqual(&bls_id.v, &id) != 0)
+            {
+                continue;
+            }
+
+            bls_ids.push(BlsId { v: id });
+            bls_us.push(BlsPublicKey {
+                v: deserialize_g2(&hex_to_bytes(&share.u)?)?,
+            });
+        }
+
+        if bls_ids.len() < threshold {
+            return Err(format!(
+                "Not enough valid decryption shares: got {}, need {} (rejected: {})",
+                bls_ids.len(),
+                threshold,
+                rejected.join(", ")
+            ));
+        }
+
+        let mut shared_point: BlsPublicKey = mem::zeroed();
+        let ret = blsPublicKeyRecover(
+            &mut shared_point,
+            bls_us.as_ptr(),
+            bls_ids.as_ptr(),
+            bls_ids.len(),
+        );
+        if ret != 0 {
+            return Err("blsPublicKeyRecover failed for decryption shares".to_string());
+        }
+
+        decrypt_ecies_with_shared_point(encrypted, &shared_point.v)
+    }
+}
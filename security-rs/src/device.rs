@@ -1,20 +1,210 @@
-use crate::crypto::{generate_id_hex, generate_keypair_hex};
-use crate::types::{SharedDeviceData, VirtualDeviceStorage};
+use crate::crypto::utils::bytes_to_hex;
+use crate::crypto::{
+    derive_keypair_hex, derive_sk_from_path, generate_id_hex, generate_keypair_hex,
+    mnemonic_to_seed, serialize_fr, sign, verify,
+};
+use crate::types::{KeyPair, RotationCertificate, SharedDeviceData, VirtualDeviceStorage};
+use std::time::{SystemTime, UNIX_EPOCH};
 
-pub fn generate_device_storage(device_name: &str) -> VirtualDeviceStorage {
-    let device_keys = generate_keypair_hex();
-    let shared_device_keys = generate_keypair_hex();
+const ROTATION_DST: &str = "SECURITY-RS-KEY-ROTATION-V1";
+
+/// EIP-2333 paths of the device key and the shared device key.
+pub const DEVICE_KEY_PATH: &str = "m/12381/7777/0/0";
+pub const SHARED_DEVICE_KEY_PATH: &str = "m/12381/7777/1/0";
+/// EIP-2333 paths whose scalars serve as the device and shared device ids.
+pub const DEVICE_ID_PATH: &str = "m/12381/7777/2/0";
+pub const SHARED_DEVICE_ID_PATH: &str = "m/12381/7777/3/0";
 
+fn device_storage(
+    device_name: &str,
+    ids: (String, String),
+    device_keys: KeyPair,
+    shared_device_keys: KeyPair,
+) -> VirtualDeviceStorage {
     VirtualDeviceStorage {
-        id: generate_id_hex(),
+        id: ids.0,
         sm: device_keys.secret_key,
         pm: device_keys.public_key,
         name: device_name.to_string(),
         shared_device_data: SharedDeviceData {
-            id: generate_id_hex(),
+            id: ids.1,
             sm: shared_device_keys.secret_key,
             pm: shared_device_keys.public_key,
             actor_shares: Vec::new(),
+            key_version: 0,
         },
+        key_version: 0,
+    }
+}
+
+pub fn generate_device_storage(device_name: &str) -> VirtualDeviceStorage {
+    device_storage(
+        device_name,
+        (generate_id_hex(), generate_id_hex()),
+        generate_keypair_hex(),
+        generate_keypair_hex(),
+    )
+}
+
+/// Deterministic id for a restored device, in the same Fr format as `generate_id_hex`.
+pub fn derive_id_hex(seed: &[u8], path: &str) -> Result<String, String> {
+    let id = derive_sk_from_path(seed, path)?;
+    Ok(bytes_to_hex(&serialize_fr(&id)))
+}
+
+/// Derives both device keys and ids from one seed, so the seed alone restores them.
+pub fn generate_device_storage_from_seed(
+    device_name: &str,
+    seed: &[u8],
+) -> Result<VirtualDeviceStorage, String> {
+    Ok(device_storage(
+        device_name,
+        (
+            derive_id_hex(seed, DEVICE_ID_PATH)?,
+            derive_id_hex(seed, SHARED_DEVICE_ID_PATH)?,
+        ),
+        derive_keypair_hex(seed, DEVICE_KEY_PATH)?,
+        derive_keypair_hex(seed, SHARED_DEVICE_KEY_PATH)?,
+    ))
+}
+
+/// Restores a device from its BIP-39 mnemonic and optional passphrase.
+pub fn restore_device_storage(
+    device_name: &str,
+    mnemonic: &str,
+    passphrase: &str,
+) -> Result<VirtualDeviceStorage, String> {
+    generate_device_storage_from_seed(device_name, &mnemonic_to_seed(mnemonic, passphrase)?)
+}
+
+fn rotation_message(
+    device_id: &str,
+    old_pm: &str,
+    new_pm: &str,
+    timestamp: u64,
+    counter: u64,
+) -> Vec<u8> {
+    format!("{ROTATION_DST}|{device_id}|{old_pm}|{new_pm}|{timestamp}|{counter}").into_bytes()
+}
+
+fn rotate_key(
+    device_id: &str,
+    keys: (&mut String, &mut String),
+    key_version: &mut u64,
+    timestamp: u64,
+) -> Result<RotationCertificate, String> {
+    let (sm, pm) = keys;
+    let new_keys = generate_keypair_hex();
+    let counter = *key_version + 1;
+    let message = rotation_message(device_id, pm, &new_keys.public_key, timestamp, counter);
+
+    let certificate = RotationCertificate {
+        device_id: device_id.to_string(),
+        old_pm: pm.clone(),
+        new_pm: new_keys.public_key.clone(),
+        timestamp,
+        counter,
+        old_signature: sign(&message, sm)?,
+        new_signature: sign(&message, &new_keys.secret_key)?,
+    };
+
+    *sm = new_keys.secret_key;
+    *pm = new_keys.public_key;
+    *key_version = counter;
+    Ok(certificate)
+}
+
+/// Replaces the device key (and, if asked, the shared device key) with fresh
+/// keys. Returns one certificate per rotated key, signed by the old and new key.
+pub fn rotate_device_keys(
+    storage: &mut VirtualDeviceStorage,
+    rotate_shared: bool,
+) -> Result<Vec<RotationCertificate>, String> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|e| format!("System clock is before the epoch: {}", e))?
+        .as_secs();
+
+    let mut certificates = vec![rotate_key(
+        &storage.id,
+        (&mut storage.sm, &mut storage.pm),
+        &mut storage.key_version,
+        timestamp,
+    )?];
+
+    if rotate_shared {
+        let shared = &mut storage.shared_device_data;
+        certificates.push(rotate_key(
+            &shared.id,
+            (&mut shared.sm, &mut shared.pm),
+            &mut shared.key_version,
+            timestamp,
+        )?);
     }
+
+    Ok(certificates)
+}
+
+pub fn verify_rotation_certificate(certificate: &RotationCertificate) -> Result<(), String> {
+    let message = rotation_message(
+        &certificate.device_id,
+        &certificate.old_pm,
+        &certificate.new_pm,
+        certificate.timestamp,
+        certificate.counter,
+    );
+
+    if !verify(&message, &certificate.old_signature, &certificate.old_pm)? {
+        return Err("Rotation certificate has an invalid old key signature".to_string());
+    }
+    if !verify(&message, &certificate.new_signature, &certificate.new_pm)? {
+        return Err("Rotation certificate has an invalid new key signature".to_string());
+    }
+    Ok(())
+}
+
+/// Follows a device identity from `initial_pm` through consecutive rotation
+/// certificates and returns the current public key.
+pub fn verify_rotation_chain(
+    device_id: &str,
+    initial_pm: &str,
+    certificates: &[RotationCertificate],
+) -> Result<String, String> {
+    let mut current_pm = initial_pm.to_string();
+    let mut previous: Option<&RotationCertificate> = None;
+
+    for certificate in certificates {
+        if certificate.device_id != device_id {
+            return Err(format!(
+                "Rotation certificate {} belongs to another device",
+                certificate.counter
+            ));
+        }
+        if certificate.old_pm != current_pm {
+            return Err(format!(
+                "Rotation certificate {} does not continue the chain",
+                certificate.counter
+            ));
+        }
+        if let Some(previous) = previous {
+            if certificate.counter != previous.counter + 1 {
+                return Err(format!(
+                    "Rotation counter jumps from {} to {}",
+                    previous.counter, certificate.counter
+                ));
+            }
+            if certificate.timestamp < previous.timestamp {
+                return Err(format!(
+                    "Rotation certificate {} predates its predecessor",
+                    certificate.counter
+                ));
+            }
+        }
+
+        verify_rotation_certificate(certificate)?;
+        current_pm = certificate.new_pm.clone();
+        previous = Some(certificate);
+    }
+
+    Ok(current_pm)
 }
@@ -0,0 +1,323 @@
+use crate::types::{ActorShare, StoredDevice, VirtualDeviceStorage};
+use aes::cipher::{block_padding::Pkcs7, BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use hmac::{Hmac, Mac};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::Sha512;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+type Aes256CbcEnc = cbc::Encryptor<aes::Aes256>;
+type Aes256CbcDec = cbc::Decryptor<aes::Aes256>;
+type HmacSha512 = Hmac<Sha512>;
+
+const MAGIC: &[u8; 4] = b"SDKS";
+const VERSION: u8 = 1;
+const SALT_LENGTH_IN_BYTE: usize = 16;
+const IV_LENGTH_IN_BYTE: usize = 16;
+const KEY_LENGTH_IN_BYTE: usize = 32;
+const MAC_LENGTH_IN_BYTE: usize = 64;
+// magic | version | log_n | r | p | salt | iv
+const HEADER_LENGTH_IN_BYTE: usize = 4 + 1 + 1 + 4 + 4 + SALT_LENGTH_IN_BYTE + IV_LENGTH_IN_BYTE;
+// Refuse headers that would make loading allocate more than 1 GiB
+const MAX_SCRYPT_MEMORY_IN_BYTE: u64 = 1 << 30;
+// and run the memory-hard loop more than a bounded number of times
+const MAX_SCRYPT_P: u32 = 16;
+const MAX_SCRYPT_R_TIMES_P: u64 = 128;
+
+#[derive(Debug)]
+pub enum KeystoreError {
+    Io(io::Error),
+    /// Another writer holds the lock file next to the keystore.
+    Locked(PathBuf),
+    InvalidFormat(String),
+    UnsupportedVersion(u8),
+    InvalidKdfParams(String),
+    /// The MAC did not match: wrong password or modified file.
+    Authentication,
+    Serialization(String),
+}
+
+impl fmt::Display for KeystoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeystoreError::Io(e) => write!(f, "Keystore I/O error: {}", e),
+            KeystoreError::Locked(path) => write!(f, "Keystore is locked: {}", path.display()),
+            KeystoreError::InvalidFormat(reason) => write!(f, "Invalid keystore: {}", reason),
+            KeystoreError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported keystore version: {}", version)
+            }
+            KeystoreError::InvalidKdfParams(reason) => {
+                write!(f, "Invalid KDF parameters: {}", reason)
+            }
+            KeystoreError::Authentication => {
+                write!(f, "Wrong password or keystore has been tampered with")
+            }
+            KeystoreError::Serialization(reason) => {
+                write!(f, "Failed to serialize keystore: {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for KeystoreError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            KeystoreError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for KeystoreError {
+    fn from(e: io::Error) -> Self {
+        KeystoreError::Io(e)
+    }
+}
+
+/// scrypt cost parameters, stored in the keystore header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        // 32 MiB, the scrypt recommendation for interactive logins
+        KdfParams {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+impl KdfParams {
+    fn validate(&self) -> Result<scrypt::Params, KeystoreError> {
+        if self.log_n > 32
+            || (128 * self.r as u128) << self.log_n > MAX_SCRYPT_MEMORY_IN_BYTE as u128
+        {
+            return Err(KeystoreError::InvalidKdfParams(
+                "memory cost too high".to_string(),
+            ));
+        }
+        if self.p > MAX_SCRYPT_P || self.r as u64 * self.p as u64 > MAX_SCRYPT_R_TIMES_P {
+            return Err(KeystoreError::InvalidKdfParams(
+                "parallelism cost too high".to_string(),
+            ));
+        }
+
+        scrypt::Params::new(self.log_n, self.r, self.p, KEY_LENGTH_IN_BYTE * 2)
+            .map_err(|e| KeystoreError::InvalidKdfParams(e.to_string()))
+    }
+}
+
+struct Header {
+    kdf: KdfParams,
+    salt: [u8; SALT_LENGTH_IN_BYTE],
+    iv: [u8; IV_LENGTH_IN_BYTE],
+}
+
+impl Header {
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LENGTH_IN_BYTE);
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.push(self.kdf.log_n);
+        bytes.extend_from_slice(&self.kdf.r.to_be_bytes());
+        bytes.extend_from_slice(&self.kdf.p.to_be_bytes());
+        bytes.extend_from_slice(&self.salt);
+        bytes.extend_from_slice(&self.iv);
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, KeystoreError> {
+        if bytes.len() < 5 || &bytes[..4] != MAGIC {
+            return Err(KeystoreError::InvalidFormat("missing magic".to_string()));
+        }
+        if bytes[4] != VERSION {
+            return Err(KeystoreError::UnsupportedVersion(bytes[4]));
+        }
+        if bytes.len() < HEADER_LENGTH_IN_BYTE {
+            return Err(KeystoreError::InvalidFormat("truncated header".to_string()));
+        }
+
+        let mut salt = [0u8; SALT_LENGTH_IN_BYTE];
+        let mut iv = [0u8; IV_LENGTH_IN_BYTE];
+        salt.copy_from_slice(&bytes[14..14 + SALT_LENGTH_IN_BYTE]);
+        iv.copy_from_slice(&bytes[14 + SALT_LENGTH_IN_BYTE..HEADER_LENGTH_IN_BYTE]);
+
+        Ok(Header {
+            kdf: KdfParams {
+                log_n: bytes[5],
+                r: u32::from_be_bytes(bytes[6..10].try_into().unwrap()),
+                p: u32::from_be_bytes(bytes[10..14].try_into().unwrap()),
+            },
+            salt,
+            iv,
+        })
+    }
+}
+
+fn derive_keys(password: &str, header: &Header) -> Result<Vec<u8>, KeystoreError> {
+    let params = header.kdf.validate()?;
+    let mut keys = vec![0u8; KEY_LENGTH_IN_BYTE * 2];
+    scrypt::scrypt(password.as_bytes(), &header.salt, &params, &mut keys)
+        .map_err(|e| KeystoreError::InvalidKdfParams(e.to_string()))?;
+    Ok(keys)
+}
+
+fn keystore_mac(mac_key: &[u8], header: &[u8], cipher_data: &[u8]) -> HmacSha512 {
+    let mut mac = HmacSha512::new_from_slice(mac_key).expect("HMAC accepts any key length");
+    mac.update(header);
+    mac.update(cipher_data);
+    mac
+}
+
+/// Encrypts a device and its actor shares into the keystore file format.
+pub fn encrypt_device(
+    device: &StoredDevice,
+    password: &str,
+    kdf: KdfParams,
+) -> Result<Vec<u8>, KeystoreError> {
+    let mut header = Header {
+        kdf,
+        salt: [0u8; SALT_LENGTH_IN_BYTE],
+        iv: [0u8; IV_LENGTH_IN_BYTE],
+    };
+    OsRng.fill_bytes(&mut header.salt);
+    OsRng.fill_bytes(&mut header.iv);
+
+    let plaintext =
+        serde_json::to_vec(device).map_err(|e| KeystoreError::Serialization(e.to_string()))?;
+    let keys = derive_keys(password, &header)?;
+    let (encryption_key, mac_key) = keys.split_at(KEY_LENGTH_IN_BYTE);
+
+    let header_bytes = header.to_bytes();
+    let cipher_data = Aes256CbcEnc::new_from_slices(encryption_key, &header.iv)
+        .map_err(|e| KeystoreError::InvalidFormat(e.to_string()))?
+        .encrypt_padded_vec_mut::<Pkcs7>(&plaintext);
+    let mac = keystore_mac(mac_key, &header_bytes, &cipher_data)
+        .finalize()
+        .into_bytes();
+
+    let mut bytes = header_bytes;
+    bytes.extend_from_slice(&cipher_data);
+    bytes.extend_from_slice(&mac);
+    Ok(bytes)
+}
+
+/// Decrypts bytes produced by [`encrypt_device`].
+pub fn decrypt_device(bytes: &[u8], password: &str) -> Result<StoredDevice, KeystoreError> {
+    let header = Header::from_bytes(bytes)?;
+    if bytes.len() < HEADER_LENGTH_IN_BYTE + MAC_LENGTH_IN_BYTE {
+        return Err(KeystoreError::InvalidFormat("truncated body".to_string()));
+    }
+    let (header_bytes, body) = bytes.split_at(HEADER_LENGTH_IN_BYTE);
+    let (cipher_data, mac) = body.split_at(body.len() - MAC_LENGTH_IN_BYTE);
+
+    let keys = derive_keys(password, &header)?;
+    let (encryption_key, mac_key) = keys.split_at(KEY_LENGTH_IN_BYTE);
+
+    keystore_mac(mac_key, header_bytes, cipher_data)
+        .verify_slice(mac)
+        .map_err(|_| KeystoreError::Authentication)?;
+
+    let plaintext = Aes256CbcDec::new_from_slices(encryption_key, &header.iv)
+        .map_err(|e| KeystoreError::InvalidFormat(e.to_string()))?
+        .decrypt_padded_vec_mut::<Pkcs7>(cipher_data)
+        .map_err(|_| KeystoreError::Authentication)?;
+
+    serde_json::from_slice(&plaintext).map_err(|e| KeystoreError::Serialization(e.to_string()))
+}
+
+struct LockFile(PathBuf);
+
+impl LockFile {
+    fn acquire(path: &Path) -> Result<Self, KeystoreError> {
+        let lock_path = with_suffix(path, ".lock");
+        match OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&lock_path)
+        {
+            Ok(_) => Ok(LockFile(lock_path)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                Err(KeystoreError::Locked(lock_path))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+impl Drop for LockFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Writes the encrypted device to `path`, replacing it atomically.
+///
+/// A `<path>.lock` file guards against concurrent writers; if it is left
+/// behind by a crashed process it has to be removed by hand.
+pub fn save_device_with_params(
+    path: &Path,
+    storage: &VirtualDeviceStorage,
+    actor_shares: &[ActorShare],
+    password: &str,
+    kdf: KdfParams,
+) -> Result<(), KeystoreError> {
+    let device = StoredDevice {
+        storage: storage.clone(),
+        actor_shares: actor_shares.to_vec(),
+    };
+    let bytes = encrypt_device(&device, password, kdf)?;
+
+    let _lock = LockFile::acquire(path)?;
+    let tmp_path = with_suffix(path, ".tmp");
+    let result = (|| {
+        let mut options = OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        let mut file = options.open(&tmp_path)?;
+        file.write_all(&bytes)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+    }
+    result?;
+
+    // Persist the rename itself
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        if let Ok(dir) = File::open(dir) {
+            let _ = dir.sync_all();
+        }
+    }
+    Ok(())
+}
+
+pub fn save_device(
+    path: &Path,
+    storage: &VirtualDeviceStorage,
+    actor_shares: &[ActorShare],
+    password: &str,
+) -> Result<(), KeystoreError> {
+    save_device_with_params(path, storage, actor_shares, password, KdfParams::default())
+}
+
+pub fn load_device(path: &Path, password: &str) -> Result<StoredDevice, KeystoreError> {
+    decrypt_device(&fs::read(path)?, password)
+}
@@ -1,15 +1,35 @@
+#[cfg(feature = "api")]
+pub mod api;
+#[cfg(feature = "capi")]
+pub mod capi;
 pub mod crypto;
 pub mod device;
+pub mod keystore;
+#[cfg(feature = "napi")]
+pub mod node;
 pub mod types;
+pub mod virtual_device;
 
 pub use crypto::generate_id_hex;
 pub use crypto::generate_keypair_hex;
 pub use crypto::init_bls;
 pub use crypto::threshold::{
-    calculate_threshold_keys, generate_actor_share, generate_contribution,
+    calculate_threshold_keys, combine_decryption_shares, combine_partial_signatures,
+    compute_public_shares, generate_actor_share, generate_contribution, generate_decryption_share,
+    generate_recovered_actor_share, generate_recovery_contribution, generate_refresh_contribution,
+    generate_repair_contribution, generate_repair_share, generate_reshare_contribution,
+    generate_reshared_actor_share, recover_repaired_actor_share, refresh_actor_share,
+    threshold_key_from_actor_share, threshold_key_from_bytes, threshold_key_to_bytes,
+    verify_decryption_share, verify_partial, verify_repair_contribution,
+    verify_reshare_contribution,
 };
-pub use device::generate_device_storage;
+pub use device::{
+    generate_device_storage, generate_device_storage_from_seed, restore_device_storage,
+    rotate_device_keys, verify_rotation_certificate, verify_rotation_chain,
+};
+pub use keystore::{load_device, save_device, KeystoreError};
 pub use types::*;
+pub use virtual_device::VirtualDevice;
 
 #[cfg(test)]
 mod tests {
@@ -29,6 +49,82 @@ mod tests {
         });
     }
 
+    fn setup_actor(
+        threshold: usize,
+        num_parties: usize,
+    ) -> (Vec<(String, KeyPair)>, Vec<Member>, Vec<ActorShare>) {
+        setup_actor_with_roles(threshold, &vec![MemberRole::Device; num_parties])
+    }
+
+    fn setup_actor_with_roles(
+        threshold: usize,
+        roles: &[MemberRole],
+    ) -> (Vec<(String, KeyPair)>, Vec<Member>, Vec<ActorShare>) {
+        let parties: Vec<(String, KeyPair)> = roles
+            .iter()
+            .map(|_| (generate_id_hex(), generate_keypair_hex()))
+            .collect();
+
+        let members: Vec<Member> = parties
+            .iter()
+            .zip(roles)
+            .map(|((id, keypair), role)| Member {
+                id: id.clone(),
+                pm: keypair.public_key.clone(),
+                role: *role,
+            })
+            .collect();
+
+        let contributions = parties
+            .iter()
+            .map(|(id, _)| ReceivedContribution {
+                sender_id: id.clone(),
+                contribution: generate_contribution(threshold, &members)
+                    .expect("Failed to generate contribution"),
+            })
+            .collect();
+
+        let actor_contract = ActorContract {
+            threshold,
+            new_members: members.clone(),
+            contributions,
+            actor_share: ActorShareData {
+                share_code: "test-share-123".to_string(),
+                subject_actor_id: "subject-actor-001".to_string(),
+                hat_id: "hat-001".to_string(),
+                from_actor_id: "from-actor-001".to_string(),
+                to_actor_id: "to-actor-001".to_string(),
+                owner_actor_id: "owner-actor-001".to_string(),
+            },
+        };
+
+        let shares = parties
+            .iter()
+            .map(|(id, keypair)| {
+                generate_actor_share("actor-1", &actor_contract, id, &keypair.secret_key)
+                    .expect("Failed to generate actor share")
+            })
+            .collect();
+
+        (parties, members, shares)
+    }
+
+    fn recover_group_secret(ids: &[String], shares: &[String]) -> String {
+        use crypto::deserialize_fr;
+
+        let ids: Vec<mclBnFr> = ids
+            .iter()
+            .map(|id| deserialize_fr(&hex::decode(id).unwrap()).unwrap())
+            .collect();
+        let shares: Vec<mclBnFr> = shares
+            .iter()
+            .map(|sh| deserialize_fr(&hex::decode(sh).unwrap()).unwrap())
+            .collect();
+
+        let secret = fr_lagrange_interpolation(&ids, &shares).expect("Failed to recover secret");
+        hex::encode(serialize_fr(&secret))
+    }
+
     #[test]
     fn test_id_generation() {
         initialize();
@@ -216,10 +312,12 @@ mod tests {
             Member {
                 id: member1_id.clone(),
                 pm: member1_kp.public_key.clone(),
+                role: MemberRole::Device,
             },
             Member {
                 id: member2_id.clone(),
                 pm: member2_kp.public_key.clone(),
+                role: MemberRole::Device,
             },
         ];
 
@@ -279,22 +377,27 @@ mod tests {
             Member {
                 id: generate_id_hex(),
                 pm: generate_keypair_hex().public_key,
+                role: MemberRole::Device,
             },
             Member {
                 id: generate_id_hex(),
                 pm: generate_keypair_hex().public_key,
+                role: MemberRole::Device,
             },
             Member {
                 id: generate_id_hex(),
                 pm: generate_keypair_hex().public_key,
+                role: MemberRole::Device,
             },
             Member {
                 id: generate_id_hex(),
                 pm: generate_keypair_hex().public_key,
+                role: MemberRole::Device,
             },
             Member {
                 id: generate_id_hex(),
                 pm: generate_keypair_hex().public_key,
+                role: MemberRole::Device,
             },
         ];
 
@@ -340,6 +443,7 @@ mod tests {
             .map(|(id, keypair)| Member {
                 id: id.clone(),
                 pm: keypair.public_key.clone(),
+                role: MemberRole::Device,
             })
             .collect();
 
@@ -386,9 +490,15 @@ mod tests {
                     assert!(!actor_share.ph.is_empty(), "PH should not be empty");
                     assert_eq!(
                         actor_share.phs.len(),
-                        threshold,
-                        "Should have public shares for threshold number of contributors"
+                        num_parties,
+                        "Should have a public share per member"
                     );
+                    let own = actor_share
+                        .phs
+                        .iter()
+                        .find(|ph| &ph.id == party_id)
+                        .unwrap();
+                    assert_eq!(own.ph, actor_share.ph);
                 }
                 Err(e) => {
                     panic!("Party {} failed to recover actor share: {}", i + 1, e);
@@ -397,6 +507,32 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_actor_share_follows_contract_order() {
+        initialize();
+
+        use crypto::{derive_public_key_g2, deserialize_fr, serialize_g2};
+
+        let threshold = 5;
+        let (parties, members, shares) = setup_actor(threshold, 8);
+
+        let ids: Vec<String> = parties.iter().map(|(id, _)| id.clone()).collect();
+        let secret_shares: Vec<String> = shares.iter().map(|share| share.sh.clone()).collect();
+        let group_secret = recover_group_secret(&ids[..threshold], &secret_shares[..threshold]);
+        let group_secret = deserialize_fr(&hex::decode(&group_secret).unwrap()).unwrap();
+        let group_key = hex::encode(serialize_g2(&derive_public_key_g2(&group_secret)));
+
+        for share in &shares {
+            assert_eq!(share.pg, shares[0].pg, "Members disagree on pg");
+            assert_eq!(share.pg[0], group_key, "pg does not match the group secret");
+        }
+
+        let public_shares = compute_public_shares(&shares[0].pg, &members).unwrap();
+        for (share, public_share) in shares.iter().zip(&public_shares) {
+            assert_eq!(share.ph, public_share.ph);
+        }
+    }
+
     #[test]
     fn test_pvsh_with_fixed_values() {
         initialize();
@@ -480,6 +616,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_mcl_public_key_vectors() {
+        initialize();
+
+        use crypto::{derive_public_key_g2, deserialize_fr, deserialize_g2, serialize_g2};
+
+        // (secret key, public key) pairs generated by herumi mcl through the
+        // app's former Dart bindings
+        let vectors = [
+            (
+                "25d2af520fadfda40c8add4916ca094bf57e0dc4dac1fb6bccdf677739fde43f",
+                "fad7810e0e4eef8e2b28df8ccaafec106a26edda9cf54aea66396851e58cde55b5ae3d26933111c4aca6c9ab8552bb0cad560e297ef957d0fc5b367eda7af8e7083796b353e7491c7a8c18137de131dbbdb4d2dcac8672f92419c9ab5fb07803",
+            ),
+            (
+                "e8ca774dfc3371d3a91e221af7ec639beb8704c7806f9b42f1f903766aa84235",
+                "3eb4427e963b4a8f485b6df67038b944cfec9016627786e3d2e27c072fbc4091345800aede6f90d8ee2c9496392a0e003e7bcf6e5799fbcde6368a5af1836b03a077b4d2ab7c4189abd8428daf76b91f3645704ec43f05c7f6694e4cbd2b9a90",
+            ),
+            (
+                "5c078a83d3977498e8f66a9c99e4ae01147b18714e9e5be7e789e2c5f7fd8855",
+                "cd145cf7cc1c3f96d254fe839d231a35ea2c5754eb3d515ddcbac9be4ce19ada843781479cda67d208aa650313e0ff0cb621f8d121f91d404e2dbe0e4ef93d48557d3cf28f603c21fc6cbc46115d3c83041dc3c4027ad26dcdb4d1d7b171438d",
+            ),
+            (
+                "f8ff1437837d4516cbd2d0484f25985bea4c4d5659a11988deb174ad09966c6e",
+                "de85a8ac8867fe4f93b72854ba33fe2a56dac2c1ca6fada28c38a282aed52e45a8f42aab6ad8ab807e560ee4b11c28131f3d2546f755c8fda7891161327cad75bca6bd2fa86464b7246a40d0624139596fa87f6410cf222a494c7570e2d33589",
+            ),
+            (
+                "963d51afb6ab2493e1e5ee58562e43e1f8aef47f980b9ba22a197cef1abfaf1a",
+                "a7fccf1965b9f01b52264e0df8a6806b956a2386d555ade094a41061864204365f9a5c812be2501aa9efa543a9a61605a97ca3fe02a40e17a8cc0b96be411adb42b696e5aedcd124a64068a3c00e0eabc7701a73c4c503bcde3114bfd37f980d",
+            ),
+        ];
+
+        for (secret_key_hex, public_key_hex) in vectors {
+            let sk = deserialize_fr(&hex::decode(secret_key_hex).unwrap()).unwrap();
+            assert_eq!(hex::encode(serialize_fr(&sk)), secret_key_hex);
+
+            let pk = derive_public_key_g2(&sk);
+            assert_eq!(hex::encode(serialize_g2(&pk)), public_key_hex);
+            let parsed = deserialize_g2(&hex::decode(public_key_hex).unwrap()).unwrap();
+            assert_eq!(hex::encode(serialize_g2(&parsed)), public_key_hex);
+        }
+    }
+
     #[test]
     fn test_id_deserialization() {
         initialize();
@@ -499,4 +677,1628 @@ mod tests {
             "ID serialization/deserialization roundtrip failed"
         );
     }
+
+    #[test]
+    fn test_refresh_actor_share() {
+        initialize();
+
+        let threshold = 2;
+        let (parties, members, shares) = setup_actor(threshold, 3);
+
+        let refresh_contributions = parties
+            .iter()
+            .map(|(id, _)| ReceivedContribution {
+                sender_id: id.clone(),
+                contribution: generate_refresh_contribution(threshold, &members)
+                    .expect("Failed to generate refresh contribution"),
+            })
+            .collect();
+
+        let refresh_contract = ActorContract {
+            threshold,
+            new_members: members.clone(),
+            contributions: refresh_contributions,
+            actor_share: ActorShareData {
+                share_code: "test-share-123".to_string(),
+                subject_actor_id: "subject-actor-001".to_string(),
+                hat_id: "hat-001".to_string(),
+                from_actor_id: "from-actor-001".to_string(),
+                to_actor_id: "to-actor-001".to_string(),
+                owner_actor_id: "owner-actor-001".to_string(),
+            },
+        };
+
+        let refreshed: Vec<ActorShare> = parties
+            .iter()
+            .zip(&shares)
+            .map(|((id, keypair), share)| {
+                refresh_actor_share(share, &refresh_contract, id, &keypair.secret_key)
+                    .expect("Failed to refresh actor share")
+            })
+            .collect();
+
+        for (old, new) in shares.iter().zip(&refreshed) {
+            assert_eq!(new.epoch, old.epoch + 1);
+            assert_eq!(new.pg[0], old.pg[0], "Group key must not change");
+            assert_ne!(new.sh, old.sh, "Share should change on refresh");
+            assert_ne!(new.ph, old.ph, "Public share should change on refresh");
+            assert_eq!(new.phs.len(), members.len());
+        }
+
+        let ids: Vec<String> = parties.iter().map(|(id, _)| id.clone()).collect();
+        let old_secret =
+            recover_group_secret(&ids[..2], &[shares[0].sh.clone(), shares[1].sh.clone()]);
+        let new_secret = recover_group_secret(
+            &ids[1..],
+            &[refreshed[1].sh.clone(), refreshed[2].sh.clone()],
+        );
+        assert_eq!(old_secret, new_secret, "Group secret changed on refresh");
+
+        let mut bad_contract = refresh_contract.clone();
+        bad_contract.contributions[0].contribution =
+            generate_contribution(threshold, &members).unwrap();
+        assert!(refresh_actor_share(
+            &shares[0],
+            &bad_contract,
+            &parties[0].0,
+            &parties[0].1.secret_key
+        )
+        .is_err());
+
+        let mut duplicate_contract = refresh_contract.clone();
+        duplicate_contract.contributions = vec![
+            refresh_contract.contributions[0].clone(),
+            refresh_contract.contributions[0].clone(),
+        ];
+        assert!(refresh_actor_share(
+            &shares[0],
+            &duplicate_contract,
+            &parties[0].0,
+            &parties[0].1.secret_key
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_reshare_actor_to_new_members() {
+        initialize();
+
+        let old_threshold = 2;
+        let (parties, _, shares) = setup_actor(old_threshold, 3);
+        let old_public_shares = shares[0].phs.clone();
+
+        let mut new_parties = vec![parties[1].clone(), parties[2].clone()];
+        new_parties.push((generate_id_hex(), generate_keypair_hex()));
+        new_parties.push((generate_id_hex(), generate_keypair_hex()));
+        let new_members: Vec<Member> = new_parties
+            .iter()
+            .map(|(id, keypair)| Member {
+                id: id.clone(),
+                pm: keypair.public_key.clone(),
+                role: MemberRole::Device,
+            })
+            .collect();
+
+        let new_threshold = 3;
+        let reshares: Vec<ReceivedContribution> = parties[..2]
+            .iter()
+            .zip(&shares)
+            .map(|((id, _), share)| ReceivedContribution {
+                sender_id: id.clone(),
+                contribution: generate_reshare_contribution(share, new_threshold, &new_members)
+                    .expect("Failed to generate reshare"),
+            })
+            .collect();
+
+        for reshare in &reshares {
+            verify_reshare_contribution(reshare, &old_public_shares, new_threshold)
+                .expect("Reshare should verify against old public shares");
+        }
+
+        let reshare_contract = ActorContract {
+            threshold: new_threshold,
+            new_members: new_members.clone(),
+            contributions: reshares,
+            actor_share: ActorShareData {
+                share_code: "test-share-456".to_string(),
+                subject_actor_id: "subject-actor-001".to_string(),
+                hat_id: "hat-001".to_string(),
+                from_actor_id: "from-actor-001".to_string(),
+                to_actor_id: "to-actor-001".to_string(),
+                owner_actor_id: "owner-actor-001".to_string(),
+            },
+        };
+
+        let new_shares: Vec<ActorShare> = new_parties
+            .iter()
+            .map(|(id, keypair)| {
+                generate_reshared_actor_share(
+                    "actor-1",
+                    old_threshold,
+                    &old_public_shares,
+                    &reshare_contract,
+                    id,
+                    &keypair.secret_key,
+                )
+                .expect("Failed to generate reshared actor share")
+            })
+            .collect();
+
+        for share in &new_shares {
+            assert_eq!(share.pg.len(), new_threshold);
+            assert_eq!(share.pg[0], shares[0].pg[0], "Group key must not change");
+            assert_eq!(share.share_code, "test-share-456");
+            assert_eq!(share.epoch, 0);
+        }
+
+        let old_ids: Vec<String> = parties.iter().map(|(id, _)| id.clone()).collect();
+        let old_secret =
+            recover_group_secret(&old_ids[..2], &[shares[0].sh.clone(), shares[1].sh.clone()]);
+        let new_ids: Vec<String> = new_parties.iter().map(|(id, _)| id.clone()).collect();
+        let new_secret = recover_group_secret(
+            &new_ids[1..],
+            &[
+                new_shares[1].sh.clone(),
+                new_shares[2].sh.clone(),
+                new_shares[3].sh.clone(),
+            ],
+        );
+        assert_eq!(old_secret, new_secret, "Group secret changed on reshare");
+
+        let mut forged = reshare_contract.contributions[0].clone();
+        forged.contribution = generate_contribution(new_threshold, &new_members).unwrap();
+        assert!(verify_reshare_contribution(&forged, &old_public_shares, new_threshold).is_err());
+    }
+
+    #[test]
+    fn test_calculate_threshold_keys_public_shares() {
+        initialize();
+
+        let threshold = 2;
+        let (parties, members, shares) = setup_actor(threshold, 3);
+
+        let contributions: Vec<ReceivedContribution> = parties
+            .iter()
+            .map(|(id, _)| ReceivedContribution {
+                sender_id: id.clone(),
+                contribution: generate_contribution(threshold, &members).unwrap(),
+            })
+            .collect();
+        let fresh_keys = calculate_threshold_keys("actor-2", threshold, &members, &contributions)
+            .expect("Failed to calculate threshold keys");
+        assert!(fresh_keys.errors.is_empty());
+        assert_eq!(fresh_keys.group_key, fresh_keys.pg[0]);
+        assert_eq!(fresh_keys.phs.len(), members.len());
+        assert!(
+            fresh_keys.sh.is_empty(),
+            "Public keys must not carry a secret"
+        );
+
+        let actor_contract = ActorContract {
+            threshold,
+            new_members: members.clone(),
+            contributions: contributions.clone(),
+            actor_share: ActorShareData {
+                share_code: "test-share-789".to_string(),
+                subject_actor_id: "subject-actor-002".to_string(),
+                hat_id: "hat-001".to_string(),
+                from_actor_id: "from-actor-001".to_string(),
+                to_actor_id: "to-actor-001".to_string(),
+                owner_actor_id: "owner-actor-001".to_string(),
+            },
+        };
+        for (id, keypair) in &parties {
+            let share = generate_actor_share("actor-2", &actor_contract, id, &keypair.secret_key)
+                .expect("Failed to generate actor share");
+            let ph = fresh_keys.phs.iter().find(|ph| &ph.id == id).unwrap();
+            assert_eq!(
+                ph.ph, share.ph,
+                "Returned public share must match member's ph"
+            );
+        }
+
+        let mut tampered = contributions.clone();
+        tampered[0].contribution.esh[1].esh = contributions[1].contribution.esh[1].esh.clone();
+        let tampered_keys =
+            calculate_threshold_keys("actor-2", threshold, &members, &tampered).unwrap();
+        assert_eq!(tampered_keys.errors.len(), 1);
+        assert!(tampered_keys.pg.is_empty() && tampered_keys.phs.is_empty());
+        assert_eq!(
+            tampered_keys.errors[0].sender_id.as_deref(),
+            Some(parties[0].0.as_str())
+        );
+
+        let my_keys = threshold_key_from_actor_share(&shares[0], &parties[0].0);
+        let json = serde_json::to_value(&my_keys).unwrap();
+        assert_eq!(json["id"], parties[0].0.as_str());
+        assert_eq!(json["pg"], shares[0].pg[0].as_str());
+        assert!(json["phs"].is_array());
+        assert!(json["errors"].is_array());
+
+        let bytes = threshold_key_to_bytes(&my_keys).unwrap();
+        let restored = threshold_key_from_bytes(&bytes).unwrap();
+        assert_eq!(restored.sh, my_keys.sh);
+        assert_eq!(restored.group_key, my_keys.group_key);
+        assert_eq!(restored.pg, my_keys.pg);
+    }
+
+    #[test]
+    fn test_partial_sign_and_combine() {
+        initialize();
+
+        let threshold = 2;
+        let (parties, _, shares) = setup_actor(threshold, 3);
+        let public_shares = shares[0].phs.clone();
+        for share in &shares {
+            let own = public_shares
+                .iter()
+                .find(|ph| ph.id == share.member_id)
+                .expect("Every member must have a stored public share");
+            assert_eq!(own.ph, share.ph);
+        }
+        let message = b"actor operation";
+
+        let partials: Vec<PartialSignature> = shares
+            .iter()
+            .map(|share| {
+                share
+                    .partial_sign(message)
+                    .expect("Failed to partially sign")
+            })
+            .collect();
+
+        for (partial, (id, _)) in partials.iter().zip(&parties) {
+            assert_eq!(&partial.id, id);
+            let public_share = public_shares.iter().find(|ph| &ph.id == id).unwrap();
+            verify_partial(partial, public_share, message).expect("Partial should verify");
+            assert!(verify_partial(partial, public_share, b"other message").is_err());
+        }
+        assert!(verify_partial(&partials[0], &public_shares[1], message).is_err());
+
+        let combined_a = combine_partial_signatures(threshold, &partials[..2]).unwrap();
+        let combined_b = combine_partial_signatures(threshold, &partials[1..]).unwrap();
+        assert_eq!(
+            combined_a, combined_b,
+            "Threshold signatures must be unique"
+        );
+        assert!(crypto::verify(message, &combined_a, &shares[0].pg[0]).unwrap());
+        assert!(!crypto::verify(b"other message", &combined_a, &shares[0].pg[0]).unwrap());
+
+        let duplicated = vec![partials[0].clone(), partials[0].clone()];
+        assert!(combine_partial_signatures(threshold, &duplicated).is_err());
+        assert!(combine_partial_signatures(threshold, &partials[..1]).is_err());
+        assert!(combine_partial_signatures(0, &partials).is_err());
+
+        // Shares stored before member_id existed sign under their phs entry
+        let mut legacy = shares[0].clone();
+        legacy.member_id.clear();
+        let partial = legacy.partial_sign(message).unwrap();
+        assert_eq!(partial.id, parties[0].0);
+        legacy.phs.clear();
+        assert!(legacy.partial_sign(message).is_err());
+    }
+
+    #[test]
+    fn test_repair_lost_member_share() {
+        initialize();
+
+        let threshold = 2;
+        let (parties, members, mut shares) = setup_actor(threshold, 3);
+        for share in &mut shares {
+            share.epoch = 3;
+        }
+
+        let lost_id = parties[2].0.clone();
+        let helpers = members[..2].to_vec();
+        let replacement_keypair = generate_keypair_hex();
+        let replacement = Member {
+            id: lost_id.clone(),
+            pm: replacement_keypair.public_key.clone(),
+            role: MemberRole::Device,
+        };
+
+        let contributions: Vec<RepairContribution> = shares[..2]
+            .iter()
+            .map(|share| {
+                generate_repair_contribution(share, &helpers, &lost_id)
+                    .expect("Failed to generate repair contribution")
+            })
+            .collect();
+
+        let public_shares = shares[0].phs.clone();
+        for contribution in &contributions {
+            verify_repair_contribution(contribution, &helpers, &public_shares)
+                .expect("Repair contribution should verify");
+        }
+
+        let repair_shares: Vec<RepairShare> = shares[..2]
+            .iter()
+            .zip(&parties)
+            .map(|(share, (_, keypair))| {
+                generate_repair_share(
+                    share,
+                    &keypair.secret_key,
+                    &helpers,
+                    &contributions,
+                    &replacement,
+                )
+                .expect("Failed to generate repair share")
+            })
+            .collect();
+
+        let mut keys = threshold_key_from_actor_share(&shares[0], "");
+        keys.sh = String::new();
+        keys.ph = String::new();
+        let actor_share_data = ActorShareData {
+            share_code: shares[2].share_code.clone(),
+            subject_actor_id: shares[2].subject_actor_id.clone(),
+            hat_id: shares[2].hat_id.clone(),
+            from_actor_id: shares[2].from_actor_id.clone(),
+            to_actor_id: shares[2].to_actor_id.clone(),
+            owner_actor_id: shares[2].owner_actor_id.clone(),
+        };
+
+        let repaired = recover_repaired_actor_share(
+            &keys,
+            &actor_share_data,
+            &repair_shares,
+            &lost_id,
+            &replacement_keypair.secret_key,
+        )
+        .expect("Failed to recover repaired share");
+        assert_eq!(
+            repaired.sh, shares[2].sh,
+            "Repaired share must equal the lost one"
+        );
+        assert_eq!(repaired.ph, shares[2].ph);
+        assert_eq!(
+            repaired.epoch, shares[2].epoch,
+            "Repair must keep the epoch"
+        );
+
+        let mut stale = repair_shares.clone();
+        stale[1].epoch = 2;
+        assert!(recover_repaired_actor_share(
+            &keys,
+            &actor_share_data,
+            &stale,
+            &lost_id,
+            &replacement_keypair.secret_key,
+        )
+        .is_err());
+
+        let mut tampered = contributions.clone();
+        tampered[0].parts[0].ph = tampered[0].parts[1].ph.clone();
+        assert!(verify_repair_contribution(&tampered[0], &helpers, &public_shares).is_err());
+        assert!(generate_repair_contribution(&shares[0], &helpers, &parties[1].0).is_err());
+    }
+
+    #[test]
+    fn test_feldman_vss() {
+        initialize();
+
+        use crypto::{feldman_reconstruct, feldman_split, feldman_verify_share};
+
+        unsafe {
+            let mut secret: mclBnFr = mem::zeroed();
+            mclBnFr_setByCSPRNG(&mut secret);
+
+            let ids: Vec<mclBnFr> = (1..=5)
+                .map(|i| {
+                    let mut id: mclBnFr = mem::zeroed();
+                    mclBnFr_setInt(&mut id, i);
+                    id
+                })
+                .collect();
+
+            let sharing = feldman_split(&secret, 3, &ids).expect("Failed to split secret");
+            assert_eq!(sharing.commitments.len(), 3);
+            assert_eq!(sharing.shares.len(), 5);
+
+            for (id, share) in ids.iter().zip(&sharing.shares) {
+                feldman_verify_share(&sharing.commitments, id, share)
+                    .expect("Share should verify against commitments");
+            }
+            assert!(
+                feldman_verify_share(&sharing.commitments, &ids[0], &sharing.shares[1]).is_err()
+            );
+
+            let recovered = feldman_reconstruct(
+                3,
+                &[ids[4], ids[1], ids[3]],
+                &[sharing.shares[4], sharing.shares[1], sharing.shares[3]],
+            )
+            .expect("Failed to reconstruct secret");
+            assert_eq!(serialize_fr(&recovered), serialize_fr(&secret));
+
+            let zero: mclBnFr = mem::zeroed();
+            assert!(feldman_reconstruct(2, &[ids[0], ids[0]], &sharing.shares[..2]).is_err());
+            assert!(feldman_reconstruct(2, &[zero, ids[1]], &sharing.shares[..2]).is_err());
+            assert!(feldman_reconstruct(3, &ids[..2], &sharing.shares[..2]).is_err());
+            assert!(feldman_split(&secret, 2, &[ids[0], ids[0]]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_split_and_combine_secret_bytes() {
+        initialize();
+
+        use crypto::{combine_secret_bytes, split_secret_bytes};
+
+        let secret: Vec<u8> = (0..=255u8).cycle().take(100).collect();
+        let shares = split_secret_bytes(&secret, 3, 5).expect("Failed to split secret");
+        assert_eq!(shares.len(), 5);
+
+        let subset = vec![shares[4].clone(), shares[0].clone(), shares[2].clone()];
+        assert_eq!(combine_secret_bytes(&subset).unwrap(), secret);
+        assert_eq!(combine_secret_bytes(&shares).unwrap(), secret);
+
+        let empty_shares = split_secret_bytes(b"", 2, 2).unwrap();
+        assert_eq!(
+            combine_secret_bytes(&empty_shares).unwrap(),
+            Vec::<u8>::new()
+        );
+
+        assert!(combine_secret_bytes(&shares[..2]).is_err());
+
+        let mut corrupted = subset.clone();
+        let mut bytes = hex::decode(&corrupted[1]).unwrap();
+        bytes[20] ^= 0x01;
+        corrupted[1] = hex::encode(bytes);
+        assert!(combine_secret_bytes(&corrupted).is_err());
+
+        let other = split_secret_bytes(&secret, 3, 5).unwrap();
+        let mixed = vec![shares[0].clone(), shares[1].clone(), other[2].clone()];
+        assert!(combine_secret_bytes(&mixed).is_err());
+
+        let duplicated = vec![shares[0].clone(), shares[0].clone(), shares[1].clone()];
+        assert!(combine_secret_bytes(&duplicated).is_err());
+    }
+
+    #[test]
+    fn test_threshold_ecies_decryption() {
+        initialize();
+
+        let keypair = generate_keypair_hex();
+        let plaintext = b"sealed for a single device";
+        let encrypted = crypto::encrypt_ecies(plaintext, &keypair.public_key).unwrap();
+        let decrypted = crypto::decrypt_ecies(&encrypted, &keypair.secret_key).unwrap();
+        assert_eq!(decrypted, plaintext);
+        let other = generate_keypair_hex();
+        assert!(crypto::decrypt_ecies(&encrypted, &other.secret_key).is_err());
+
+        let threshold = 2;
+        let (_, _, shares) = setup_actor(threshold, 3);
+        let public_shares = shares[0].phs.clone();
+        let message = b"sealed for the actor group";
+        let encrypted = crypto::encrypt_ecies(message, &shares[0].pg[0]).unwrap();
+
+        let decryption_shares: Vec<DecryptionShare> = shares
+            .iter()
+            .map(|share| generate_decryption_share(share, &encrypted).unwrap())
+            .collect();
+        for (decryption_share, public_share) in decryption_shares.iter().zip(&public_shares) {
+            verify_decryption_share(decryption_share, public_share, &encrypted)
+                .expect("Decryption share should verify");
+        }
+
+        let decrypted_a = combine_decryption_shares(
+            &encrypted,
+            threshold,
+            &decryption_shares[..2],
+            &public_shares,
+        )
+        .unwrap();
+        let decrypted_b = combine_decryption_shares(
+            &encrypted,
+            threshold,
+            &decryption_shares[1..],
+            &public_shares,
+        )
+        .unwrap();
+        assert_eq!(decrypted_a, message);
+        assert_eq!(decrypted_b, message);
+
+        let mut tampered = decryption_shares.clone();
+        tampered[0].u = decryption_shares[1].u.clone();
+        assert!(verify_decryption_share(&tampered[0], &public_shares[0], &encrypted).is_err());
+        assert!(
+            combine_decryption_shares(&encrypted, threshold, &tampered[..2], &public_shares)
+                .is_err()
+        );
+        let decrypted_c =
+            combine_decryption_shares(&encrypted, threshold, &tampered, &public_shares).unwrap();
+        assert_eq!(decrypted_c, message);
+    }
+
+    #[test]
+    fn test_ecies_fixed_vector() {
+        initialize();
+
+        use crypto::{decrypt_ecies, derive_public_key_g2, deserialize_fr, serialize_g2};
+
+        // Fixed values from the TS Hiver spec
+        let secret_key_hex = "e298123a116d8eaced9f32f3e6482debe93074008b06670106ade44fe29b7245";
+        let public_key_hex = "64e00bdbbeffc40a1012a7d44a5a02cb62d09859c4c7bb1de29039a8acd3eb8739f3491d9e69d2e84b8f5019bab4aa06e94e9ff25563f1d2c6f769e3a1df4a17444c2b1e645cf9b3cbb5bdef9ad2ea4e940a9c7caa0e6613eabb4f8073da8e00";
+        let encrypted = EncryptedData {
+            iv: "97be2c68b6dd250ba3d011337b993e26".to_string(),
+            mac: "894ee6b6d016cc82ac8e097a435071d65fcfc012f3c3a3c322c8a3c0c38a019e62dad4f302c3468d028dd1b2342c831f5a578e1b0d4e6bbaf482d1d6fa00b8d9".to_string(),
+            cipher_data: "168dde23c5a2252c127f821cefd034d1edc7c5c72c123b4ec9c73d0bea56900b63be3ca42f35ad12edf5cfebe819a59d665bfad03183a5bb6da18c528379c86a".to_string(),
+            ephemeral_public_key: "162703bd9b1040ac5c4b4f2ca57f488c54163172cf1465e759b6b1c358e3456de24d17a83b4d61cc1a45fa333cd13a0ae4e55e7f61e08a3077f6da94fa1854b750c2cfbcabe9e0d69b8c8e84dccc0b64353412f041d2fd6f4cf762056eec3c17".to_string(),
+        };
+
+        let sk = deserialize_fr(&hex::decode(secret_key_hex).unwrap()).unwrap();
+        assert_eq!(
+            hex::encode(serialize_g2(&derive_public_key_g2(&sk))),
+            public_key_hex
+        );
+
+        let decrypted = decrypt_ecies(&encrypted, secret_key_hex).expect("Decryption failed");
+        assert_eq!(
+            String::from_utf8(decrypted).unwrap(),
+            r#"{"displayName":"Non-Legal User","email":"user_3302@natrix.io"}"#
+        );
+    }
+
+    #[test]
+    fn test_nizk_proofs() {
+        initialize();
+
+        unsafe {
+            let mut secret: mclBnFr = mem::zeroed();
+            mclBnFr_setByCSPRNG(&mut secret);
+            let mut other: mclBnFr = mem::zeroed();
+            mclBnFr_setByCSPRNG(&mut other);
+
+            let g1 = crypto::hash_to_g1(b"nizk test base").unwrap();
+            let g2 = crypto::get_g2_generator();
+            let mut public_g1: mclBnG1 = mem::zeroed();
+            mclBnG1_mul(&mut public_g1, &g1, &secret);
+            let public_g2 = crypto::derive_public_key_g2(&secret);
+            let other_g2 = crypto::derive_public_key_g2(&other);
+
+            let domain = b"security-rs/test/key-ownership";
+            let proof_g1 = crypto::prove_dlog(domain, &g1, &secret).unwrap();
+            crypto::verify_dlog(domain, &g1, &public_g1, &proof_g1).unwrap();
+            let proof_g2 = crypto::prove_dlog(domain, &g2, &secret).unwrap();
+            crypto::verify_dlog(domain, &g2, &public_g2, &proof_g2).unwrap();
+            assert!(crypto::verify_dlog(domain, &g2, &other_g2, &proof_g2).is_err());
+            assert!(crypto::verify_dlog(b"other-domain", &g2, &public_g2, &proof_g2).is_err());
+            assert!(crypto::verify_dlog(domain, &g2, &public_g2, "00.11.22").is_err());
+
+            let proof = crypto::prove_dleq(domain, &g1, &g2, &secret).unwrap();
+            crypto::verify_dleq(domain, &g1, &public_g1, &g2, &public_g2, &proof).unwrap();
+            assert!(crypto::verify_dleq(domain, &g1, &public_g1, &g2, &other_g2, &proof).is_err());
+            assert!(
+                crypto::verify_dleq(b"other-domain", &g1, &public_g1, &g2, &public_g2, &proof)
+                    .is_err()
+            );
+
+            let mut other_g1: mclBnG1 = mem::zeroed();
+            mclBnG1_mul(&mut other_g1, &g1, &other);
+            let forged = crypto::prove_dleq(domain, &g1, &g2, &other).unwrap();
+            assert!(crypto::verify_dleq(domain, &g1, &other_g1, &g2, &public_g2, &forged).is_err());
+        }
+    }
+
+    #[test]
+    fn test_identity_based_encryption() {
+        use sha2::{Digest, Sha512};
+
+        initialize();
+
+        let threshold = 2;
+        let (_, _, shares) = setup_actor(threshold, 3);
+        let public_shares = shares[0].phs.clone();
+        let identity = "hat:treasurer";
+        let message = b"for whoever wears the hat";
+        let encrypted = crypto::encrypt_ibe(message, identity, &shares[0].pg[0]).unwrap();
+
+        let key_shares: Vec<IdentityKeyShare> = shares
+            .iter()
+            .map(|share| crypto::generate_identity_key_share(share, identity).unwrap())
+            .collect();
+        for (key_share, share) in key_shares.iter().zip(&shares) {
+            let mut hashed = b"SECURITY-RS-IBE-IDENTITY-V2".to_vec();
+            hashed.extend_from_slice(identity.as_bytes());
+            for signed in [hashed.clone(), Sha512::digest(&hashed).to_vec()] {
+                let signature = crypto::sign_direct(&signed, &share.sh).unwrap();
+                assert_ne!(
+                    signature, key_share.key,
+                    "Signing must not yield identity keys"
+                );
+            }
+        }
+        for (key_share, public_share) in key_shares.iter().zip(&public_shares) {
+            crypto::verify_identity_key_share(key_share, public_share, identity)
+                .expect("Identity key share should verify");
+            assert!(
+                crypto::verify_identity_key_share(key_share, public_share, "hat:other").is_err()
+            );
+        }
+
+        let key_a = crypto::combine_identity_key_shares(
+            identity,
+            threshold,
+            &key_shares[..2],
+            &public_shares,
+        )
+        .unwrap();
+        let key_b = crypto::combine_identity_key_shares(
+            identity,
+            threshold,
+            &key_shares[1..],
+            &public_shares,
+        )
+        .unwrap();
+        assert_eq!(key_a, key_b);
+        assert_eq!(crypto::decrypt_ibe(&encrypted, &key_a).unwrap(), message);
+
+        let other_shares: Vec<IdentityKeyShare> = shares
+            .iter()
+            .map(|share| crypto::generate_identity_key_share(share, "hat:other").unwrap())
+            .collect();
+        assert!(crypto::combine_identity_key_shares(
+            identity,
+            threshold,
+            &other_shares,
+            &public_shares
+        )
+        .is_err());
+        let other_key = crypto::combine_identity_key_shares(
+            "hat:other",
+            threshold,
+            &other_shares,
+            &public_shares,
+        )
+        .unwrap();
+        assert!(crypto::decrypt_ibe(&encrypted, &other_key).is_err());
+    }
+
+    #[test]
+    fn test_random_beacon() {
+        initialize();
+
+        let threshold = 2;
+        let (_, _, shares) = setup_actor(threshold, 3);
+        let public_shares = shares[0].phs.clone();
+        let actor_id = shares[0].actor_id.clone();
+        let group_key = shares[0].pg[0].clone();
+
+        let partials: Vec<PartialSignature> = shares
+            .iter()
+            .map(|share| crypto::generate_beacon_partial(share, 0, None).unwrap())
+            .collect();
+        let genesis = crypto::combine_beacon_partials(
+            &actor_id,
+            0,
+            None,
+            &group_key,
+            threshold,
+            &partials[..2],
+            &public_shares,
+        )
+        .unwrap();
+        let genesis_b = crypto::combine_beacon_partials(
+            &actor_id,
+            0,
+            None,
+            &group_key,
+            threshold,
+            &partials[1..],
+            &public_shares,
+        )
+        .unwrap();
+        assert_eq!(genesis.randomness, genesis_b.randomness);
+        assert_eq!(genesis.randomness.len(), 64);
+        crypto::verify_beacon_round(&genesis, &group_key, None).unwrap();
+
+        assert!(crypto::generate_beacon_partial(&shares[0], 1, None).is_err());
+        let partials: Vec<PartialSignature> = shares
+            .iter()
+            .map(|share| crypto::generate_beacon_partial(share, 1, Some(&genesis)).unwrap())
+            .collect();
+        let next = crypto::combine_beacon_partials(
+            &actor_id,
+            1,
+            Some(&genesis),
+            &group_key,
+            threshold,
+            &partials,
+            &public_shares,
+        )
+        .unwrap();
+        assert_eq!(next.previous_signature, genesis.signature);
+        assert_ne!(next.randomness, genesis.randomness);
+        crypto::verify_beacon_round(&next, &group_key, Some(&genesis)).unwrap();
+        assert!(crypto::verify_beacon_round(&next, &group_key, None).is_err());
+        assert!(crypto::verify_beacon_round(&next, &group_key, Some(&next)).is_err());
+
+        let mut forged = next.clone();
+        forged.randomness = genesis.randomness.clone();
+        assert!(crypto::verify_beacon_round(&forged, &group_key, Some(&genesis)).is_err());
+
+        assert!(crypto::combine_beacon_partials(
+            &actor_id,
+            2,
+            Some(&next),
+            &group_key,
+            threshold,
+            &partials,
+            &public_shares,
+        )
+        .is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "mcl", feature = "pure-rust"))]
+    fn test_backends_agree() {
+        initialize();
+
+        use crypto::backend::{CurveBackend, Mcl, PureRust};
+
+        assert!(PureRust::init());
+        assert_eq!(
+            Mcl::g2_serialize(&Mcl::g2_generator()),
+            PureRust::g2_serialize(&PureRust::g2_generator())
+        );
+
+        for i in 0..16u8 {
+            let message = [b"backend-agreement".as_slice(), &[i]].concat();
+
+            let x = Mcl::fr_random().unwrap();
+            let x_bytes = Mcl::fr_serialize(&x);
+            let y = PureRust::fr_deserialize(&x_bytes).unwrap();
+            assert_eq!(PureRust::fr_serialize(&y), x_bytes);
+
+            assert_eq!(
+                Mcl::fr_serialize(&Mcl::fr_hash_of(&message).unwrap()),
+                PureRust::fr_serialize(&PureRust::fr_hash_of(&message).unwrap())
+            );
+
+            let mcl_pk = Mcl::g2_mul(&Mcl::g2_generator(), &x).unwrap();
+            let rust_pk = PureRust::g2_mul(&PureRust::g2_generator(), &y).unwrap();
+            assert_eq!(Mcl::g2_serialize(&mcl_pk), PureRust::g2_serialize(&rust_pk));
+
+            let mcl_h = Mcl::g1_hash_and_map_to(&message).unwrap();
+            let rust_h = PureRust::g1_hash_and_map_to(&message).unwrap();
+            assert_eq!(Mcl::g1_serialize(&mcl_h), PureRust::g1_serialize(&rust_h));
+
+            let mcl_sig = Mcl::g1_mul(&mcl_h, &x).unwrap();
+            let rust_sig = PureRust::g1_mul(&rust_h, &y).unwrap();
+            assert_eq!(
+                Mcl::g1_serialize(&mcl_sig),
+                PureRust::g1_serialize(&rust_sig)
+            );
+
+            assert_eq!(
+                Mcl::gt_serialize(&Mcl::pairing(&mcl_h, &mcl_pk).unwrap()),
+                PureRust::gt_serialize(&PureRust::pairing(&rust_h, &rust_pk).unwrap())
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "pure-rust")]
+    fn test_pure_rust_rejects_malformed_elements() {
+        use crypto::backend::{CurveBackend, PureRust};
+
+        let one = PureRust::fr_from_int(1);
+        let bad_fr = mclBnFr { d: [u64::MAX; 4] };
+        assert!(PureRust::fr_add(&one, &bad_fr).is_none());
+        assert!(PureRust::fr_is_equal(&bad_fr, &bad_fr).is_none());
+        assert!(PureRust::fr_serialize(&bad_fr).is_empty());
+
+        let mut bad_g2 = PureRust::g2_generator();
+        bad_g2.x.d[0].d = [u64::MAX; 6];
+        assert!(PureRust::g2_mul(&bad_g2, &one).is_none());
+        assert!(PureRust::g2_serialize(&bad_g2).is_empty());
+
+        // The emulated mcl ABI reports malformed elements through its return
+        // codes rather than panicking
+        #[cfg(not(feature = "mcl"))]
+        unsafe {
+            initialize();
+
+            let mut sum: mclBnFr = mem::zeroed();
+            mclBnFr_add(&mut sum, &one, &bad_fr);
+            assert!(serialize_fr(&sum).is_empty());
+            assert_eq!(mclBnFr_isEqual(&sum, &sum), 0);
+
+            let mut point: mclBnG2 = mem::zeroed();
+            mclBnG2_mul(&mut point, &bad_g2, &one);
+            assert!(crypto::serialize_g2(&point).is_empty());
+            assert_eq!(mclBnG2_isEqual(&point, &point), 0);
+
+            let mut gt: mclBnGT = mem::zeroed();
+            mclBn_pairing(&mut gt, &crypto::hash_to_g1(b"message").unwrap(), &bad_g2);
+            let mut buf = [0u8; GT_SIZE];
+            let size = mclBnGT_serialize(buf.as_mut_ptr() as *mut _, GT_SIZE, &gt);
+            assert_eq!(size, 0);
+
+            assert!(crypto::deserialize_g2(&[0xff; 96]).is_err());
+            let mut h: mclBnG1 = mem::zeroed();
+            assert_eq!(mclBnG1_hashAndMapTo(&mut h, std::ptr::null(), 0), 0);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "capi")]
+    fn test_c_abi_json_roundtrip() {
+        use std::ffi::{CStr, CString};
+
+        fn call_c(
+            f: unsafe extern "C" fn(*const std::os::raw::c_char) -> *mut std::os::raw::c_char,
+            request: serde_json::Value,
+        ) -> serde_json::Value {
+            let request = CString::new(request.to_string()).unwrap();
+            unsafe {
+                let response = f(request.as_ptr());
+                let value = serde_json::from_str(CStr::from_ptr(response).to_str().unwrap());
+                capi::security_string_free(response);
+                value.unwrap()
+            }
+        }
+
+        let storage = call_c(
+            capi::security_generate_device_storage,
+            serde_json::json!({ "name": "ffi-device" }),
+        );
+        let storage: VirtualDeviceStorage = serde_json::from_value(storage["ok"].clone()).unwrap();
+        assert_eq!(storage.name, "ffi-device");
+
+        let data = hex::encode(b"hello from C");
+        let signature = call_c(
+            capi::security_sign,
+            serde_json::json!({ "data": data, "secret_key": storage.sm }),
+        );
+        let signature = signature["ok"].as_str().unwrap();
+        let verified = call_c(
+            capi::security_verify,
+            serde_json::json!({ "data": data, "signature": signature, "public_key": storage.pm }),
+        );
+        assert_eq!(verified["ok"], true);
+
+        let members = vec![Member {
+            id: storage.id.clone(),
+            pm: storage.pm.clone(),
+            role: MemberRole::Device,
+        }];
+        let contribution = call_c(
+            capi::security_generate_contribution,
+            serde_json::json!({ "threshold": 1, "members": members }),
+        );
+        let contribution: Contribution =
+            serde_json::from_value(contribution["ok"].clone()).unwrap();
+
+        let sh = call_c(
+            capi::security_pvsh_decode,
+            serde_json::json!({
+                "receiver_id": storage.id,
+                "receiver_pk": storage.pm,
+                "receiver_sk": storage.sm,
+                "esh": contribution.esh[0].esh,
+            }),
+        );
+        let sh = sh["ok"].as_str().unwrap();
+        let esh = call_c(
+            capi::security_pvsh_encode,
+            serde_json::json!({ "receiver_id": storage.id, "receiver_pk": storage.pm, "sh": sh }),
+        );
+        let verified = call_c(
+            capi::security_pvsh_verify,
+            serde_json::json!({
+                "receiver_id": storage.id,
+                "receiver_pk": storage.pm,
+                "ph": contribution.pg[0],
+                "esh": esh["ok"],
+            }),
+        );
+        assert_eq!(verified["ok"], true);
+
+        let keypair = call_c(capi::security_generate_keypair, serde_json::json!({}));
+        let keypair: KeyPair = serde_json::from_value(keypair["ok"].clone()).unwrap();
+        let public_key = call_c(
+            capi::security_derive_public_key,
+            serde_json::json!({ "secret_key": keypair.secret_key }),
+        );
+        assert_eq!(public_key["ok"], keypair.public_key.as_str());
+        let id = call_c(capi::security_generate_id, serde_json::json!({}));
+        assert_eq!(id["ok"].as_str().unwrap().len(), 64);
+
+        let reshare = call_c(
+            capi::security_generate_contribution,
+            serde_json::json!({ "threshold": 1, "members": members, "old_sh": keypair.secret_key }),
+        );
+        assert_eq!(reshare["ok"]["pg"][0], keypair.public_key.as_str());
+
+        let partial = call_c(
+            capi::security_sign,
+            serde_json::json!({ "data": data, "secret_key": sh }),
+        );
+        let combined = call_c(
+            capi::security_combine_signatures,
+            serde_json::json!({
+                "threshold": 1,
+                "partials": [{ "id": storage.id, "sig": partial["ok"] }],
+            }),
+        );
+        assert_eq!(combined["ok"], partial["ok"]);
+        let error = call_c(
+            capi::security_combine_signatures,
+            serde_json::json!({
+                "threshold": 2,
+                "partials": [{ "id": storage.id, "sig": partial["ok"] }],
+            }),
+        );
+        assert!(error["error"].is_string());
+
+        let error = call_c(capi::security_sign, serde_json::json!({ "data": "zz" }));
+        assert!(error["error"]
+            .as_str()
+            .unwrap()
+            .starts_with("Invalid request"));
+        unsafe {
+            let response = capi::security_sign(std::ptr::null());
+            assert!(CStr::from_ptr(response).to_str().unwrap().contains("error"));
+            capi::security_string_free(response);
+        }
+    }
+
+    #[test]
+    fn test_device_keystore() {
+        use keystore::{decrypt_device, encrypt_device, save_device_with_params, KdfParams};
+
+        initialize();
+
+        let kdf = KdfParams {
+            log_n: 10,
+            r: 8,
+            p: 1,
+        };
+        let (_, _, shares) = setup_actor(2, 3);
+        let storage = generate_device_storage("keystore");
+        let path = std::env::temp_dir().join(format!("device-{}.keystore", std::process::id()));
+
+        save_device_with_params(&path, &storage, &shares, "correct horse", kdf)
+            .expect("Failed to save device");
+        let loaded = load_device(&path, "correct horse").expect("Failed to load device");
+        assert_eq!(loaded.storage.sm, storage.sm);
+        assert_eq!(
+            loaded.storage.shared_device_data.sm,
+            storage.shared_device_data.sm
+        );
+        assert_eq!(loaded.actor_shares.len(), shares.len());
+        assert_eq!(loaded.actor_shares[0].sh, shares[0].sh);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600, "Keystore must be private");
+        }
+
+        let bytes = std::fs::read(&path).unwrap();
+        assert!(!String::from_utf8_lossy(&bytes).contains(&storage.sm));
+        assert!(matches!(
+            load_device(&path, "wrong"),
+            Err(KeystoreError::Authentication)
+        ));
+
+        let mut tampered = bytes.clone();
+        let middle = tampered.len() / 2;
+        tampered[middle] ^= 1;
+        assert!(matches!(
+            decrypt_device(&tampered, "correct horse"),
+            Err(KeystoreError::Authentication)
+        ));
+        let mut tampered = bytes.clone();
+        tampered[4] = 9;
+        assert!(matches!(
+            decrypt_device(&tampered, "correct horse"),
+            Err(KeystoreError::UnsupportedVersion(9))
+        ));
+        let mut tampered = encrypt_device(&loaded, "correct horse", kdf).unwrap();
+        tampered[5] = 40;
+        assert!(matches!(
+            decrypt_device(&tampered, "correct horse"),
+            Err(KeystoreError::InvalidKdfParams(_))
+        ));
+        let mut tampered = encrypt_device(&loaded, "correct horse", kdf).unwrap();
+        tampered[10..14].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(matches!(
+            decrypt_device(&tampered, "correct horse"),
+            Err(KeystoreError::InvalidKdfParams(_))
+        ));
+        let costly = KdfParams {
+            log_n: 1,
+            r: 64,
+            p: 4,
+        };
+        assert!(matches!(
+            encrypt_device(&loaded, "correct horse", costly),
+            Err(KeystoreError::InvalidKdfParams(_))
+        ));
+
+        let lock_path = path.with_extension("keystore.lock");
+        std::fs::write(&lock_path, b"").unwrap();
+        assert!(matches!(
+            save_device_with_params(&path, &storage, &[], "correct horse", kdf),
+            Err(KeystoreError::Locked(_))
+        ));
+        std::fs::remove_file(&lock_path).unwrap();
+        save_device_with_params(&path, &storage, &[], "new password", kdf).unwrap();
+        assert!(load_device(&path, "new password")
+            .unwrap()
+            .actor_shares
+            .is_empty());
+        assert!(!lock_path.exists());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_eip2335_vectors() {
+        use crypto::{export_eip2335, import_eip2335, Eip2335Kdf};
+
+        initialize();
+
+        let password = "\u{1d531}\u{1d522}\u{1d530}\u{1d531}\u{1d52d}\u{1d51e}\u{1d530}\u{1d530}\u{1d534}\u{1d52c}\u{1d52f}\u{1d521}\u{1f511}";
+        let mut secret =
+            hex::decode("000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f")
+                .unwrap();
+        secret.reverse();
+        let secret_key = hex::encode(&secret);
+        let pubkey = "9612d7a727c9d0a22e185a1c768478dfe919cada9266988cb32359c11f2b7b27f4ae4040902382ae2910c15e2b420d07";
+
+        let vectors = [
+            r#"{
+                "crypto": {
+                    "kdf": {
+                        "function": "scrypt",
+                        "params": {
+                            "dklen": 32,
+                            "n": 262144,
+                            "p": 1,
+                            "r": 8,
+                            "salt": "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+                        },
+                        "message": ""
+                    },
+                    "checksum": {
+                        "function": "sha256",
+                        "params": {},
+                        "message": "d2217fe5f3e9a1e34581ef8a78f7c9928e436d36dacc5e846690a5581e8ea484"
+                    },
+                    "cipher": {
+                        "function": "aes-128-ctr",
+                        "params": { "iv": "264daa3f303d7259501c93d997d84fe6" },
+                        "message": "06ae90d55fe0a6e9c5c3bc5b170827b2e5cce3929ed3f116c2811e6366dfe20f"
+                    }
+                },
+                "description": "This is a test keystore that uses scrypt to secure the secret.",
+                "pubkey": "9612d7a727c9d0a22e185a1c768478dfe919cada9266988cb32359c11f2b7b27f4ae4040902382ae2910c15e2b420d07",
+                "path": "m/12381/60/3141592653/589793238",
+                "uuid": "1d85ae20-35c5-4611-98e8-aa14a633906f",
+                "version": 4
+            }"#,
+            r#"{
+                "crypto": {
+                    "kdf": {
+                        "function": "pbkdf2",
+                        "params": {
+                            "dklen": 32,
+                            "c": 262144,
+                            "prf": "hmac-sha256",
+                            "salt": "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+                        },
+                        "message": ""
+                    },
+                    "checksum": {
+                        "function": "sha256",
+                        "params": {},
+                        "message": "8a9f5d9912ed7e75ea794bc5a89bca5f193721d30868ade6f73043c6ea6febf1"
+                    },
+                    "cipher": {
+                        "function": "aes-128-ctr",
+                        "params": { "iv": "264daa3f303d7259501c93d997d84fe6" },
+                        "message": "cee03fde2af33149775b7223e7845e4fb2c8ae1792e5f99fe9ecf474cc8c16ad"
+                    }
+                },
+                "description": "This is a test keystore that uses PBKDF2 to secure the secret.",
+                "pubkey": "9612d7a727c9d0a22e185a1c768478dfe919cada9266988cb32359c11f2b7b27f4ae4040902382ae2910c15e2b420d07",
+                "path": "m/12381/60/0/0",
+                "uuid": "64625def-3331-4eea-ab6f-782f3ed16a83",
+                "version": 4
+            }"#,
+        ];
+        for vector in vectors {
+            let keystore: Eip2335Keystore = serde_json::from_str(vector).unwrap();
+            assert_eq!(keystore.pubkey, pubkey);
+            let keypair = import_eip2335(&keystore, password).expect("Failed to import vector");
+            assert_eq!(keypair.secret_key, secret_key);
+            assert!(import_eip2335(&keystore, "testpassword").is_err());
+
+            let mut mismatched = keystore.clone();
+            mismatched.pubkey =
+                hex::encode(crypto::serialize_g1_compressed(&crypto::get_g1_generator()).unwrap());
+            assert!(import_eip2335(&mismatched, password).is_err());
+        }
+
+        let exported = export_eip2335(
+            &secret_key,
+            password,
+            "m/12381/60/0/0",
+            Eip2335Kdf::Pbkdf2 { c: 1024 },
+        )
+        .unwrap();
+        assert_eq!(exported.pubkey, pubkey);
+
+        // The generator and its negation, (r - 1)·G1, differ only in the y flag
+        let generator = crypto::serialize_g1_compressed(&crypto::get_g1_generator()).unwrap();
+        assert_eq!(hex::encode(generator), "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb");
+        let r_minus_one =
+            hex::decode("00000000fffffffffe5bfeff02a4bd5305d8a10908d83933487d9d2953a7ed73")
+                .unwrap();
+        let negated = crypto::derive_public_key_g1(&crypto::deserialize_fr(&r_minus_one).unwrap());
+        assert_eq!(hex::encode(crypto::serialize_g1_compressed(&negated).unwrap()), "b7f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb");
+
+        let keypair = generate_keypair_hex();
+        let keystore = export_eip2335(
+            &keypair.secret_key,
+            "\u{7f}pass\u{1f}word",
+            "m/12381/60/0/0",
+            Eip2335Kdf::Pbkdf2 { c: 1024 },
+        )
+        .unwrap();
+        assert_eq!(keystore.pubkey.len(), 96);
+        assert_eq!(keystore.uuid.len(), 36);
+        let json = serde_json::to_string(&keystore).unwrap();
+        let imported = import_eip2335(&serde_json::from_str(&json).unwrap(), "password").unwrap();
+        assert_eq!(imported.secret_key, keypair.secret_key);
+        assert_eq!(imported.public_key, keypair.public_key);
+
+        for c in [0, u32::MAX] {
+            assert!(export_eip2335(
+                &keypair.secret_key,
+                "password",
+                "",
+                Eip2335Kdf::Pbkdf2 { c }
+            )
+            .is_err());
+        }
+    }
+
+    fn fr_from_decimal(decimal: &str) -> mclBnFr {
+        unsafe {
+            let mut ten: mclBnFr = mem::zeroed();
+            mclBnFr_setInt(&mut ten, 10);
+            decimal.bytes().fold(mem::zeroed(), |acc, digit| {
+                let mut shifted: mclBnFr = mem::zeroed();
+                let mut digit_fr: mclBnFr = mem::zeroed();
+                let mut result: mclBnFr = mem::zeroed();
+                mclBnFr_mul(&mut shifted, &acc, &ten);
+                mclBnFr_setInt(&mut digit_fr, (digit - b'0') as i64);
+                mclBnFr_add(&mut result, &shifted, &digit_fr);
+                result
+            })
+        }
+    }
+
+    #[test]
+    fn test_eip2333_vectors() {
+        use crypto::{derive_child_sk, derive_master_sk, derive_sk_from_path};
+
+        initialize();
+
+        let vectors = [
+            (
+                "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+                "6083874454709270928345386274498605044986640685124978867557563392430687146096",
+                0,
+                "20397789859736650942317412262472558107875392172444076792671091975210932703118",
+            ),
+            (
+                "3141592653589793238462643383279502884197169399375105820974944592",
+                "29757020647961307431480504535336562678282505419141012933316116377660817309383",
+                3141592653,
+                "25457201688850691947727629385191704516744796114925897962676248250929345014287",
+            ),
+            (
+                "0099FF991111002299DD7744EE3355BBDD8844115566CC55663355668888CC00",
+                "27580842291869792442942448775674722299803720648445448686099262467207037398656",
+                4294967295,
+                "29358610794459428860402234341874281240803786294062035874021252734817515685787",
+            ),
+            (
+                "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3",
+                "19022158461524446591288038168518313374041767046816487870552872741050760015818",
+                42,
+                "31372231650479070279774297061823572166496564838472787488249775572789064611981",
+            ),
+        ];
+
+        for (seed, master_sk, index, child_sk) in vectors {
+            let seed = hex::decode(seed).unwrap();
+            let master = derive_master_sk(&seed).unwrap();
+            assert_eq!(
+                serialize_fr(&master),
+                serialize_fr(&fr_from_decimal(master_sk))
+            );
+
+            let child = derive_child_sk(&master, index);
+            assert_eq!(
+                serialize_fr(&child),
+                serialize_fr(&fr_from_decimal(child_sk))
+            );
+
+            let from_path = derive_sk_from_path(&seed, &format!("m/{}", index)).unwrap();
+            assert_eq!(serialize_fr(&from_path), serialize_fr(&child));
+        }
+
+        assert!(derive_master_sk(&[0u8; 31]).is_err());
+        assert!(derive_sk_from_path(&[0u8; 32], "12381/0").is_err());
+        assert!(derive_sk_from_path(&[0u8; 32], "m/12381/x").is_err());
+
+        let seed = [7u8; 64];
+        let storage = generate_device_storage_from_seed("restored", &seed).unwrap();
+        let restored = generate_device_storage_from_seed("restored", &seed).unwrap();
+        assert_eq!(storage.sm, restored.sm);
+        assert_eq!(storage.pm, restored.pm);
+        assert_eq!(
+            storage.shared_device_data.sm,
+            restored.shared_device_data.sm
+        );
+        assert_ne!(storage.sm, storage.shared_device_data.sm);
+        let signature = sign(b"restored", &storage.sm).unwrap();
+        assert!(crypto::verify(b"restored", &signature, &restored.pm).unwrap());
+    }
+
+    #[test]
+    fn test_mnemonic_device_restore() {
+        use crypto::{generate_mnemonic, mnemonic_to_seed, validate_mnemonic};
+
+        initialize();
+
+        let vectors = [
+            (
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+                "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            ),
+            (
+                "legal winner thank year wave sausage worth useful legal winner thank yellow",
+                "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+            ),
+        ];
+        for (phrase, seed) in vectors {
+            assert!(validate_mnemonic(phrase).is_ok());
+            assert_eq!(
+                hex::encode(mnemonic_to_seed(phrase, "TREZOR").unwrap()),
+                seed
+            );
+        }
+        assert!(validate_mnemonic(&["abandon"; 12].join(" ")).is_err());
+        assert!(validate_mnemonic("abandon abandon notaword").is_err());
+        assert!(generate_mnemonic(13).is_err());
+
+        let phrase = generate_mnemonic(24).unwrap();
+        assert_eq!(phrase.split(' ').count(), 24);
+        assert!(validate_mnemonic(&phrase).is_ok());
+
+        let storage = restore_device_storage("phone", &phrase, "pass").unwrap();
+        let restored = restore_device_storage("phone", &phrase, "pass").unwrap();
+        assert_eq!(
+            serde_json::to_value(&storage).unwrap(),
+            serde_json::to_value(&restored).unwrap()
+        );
+        assert_ne!(storage.id, storage.shared_device_data.id);
+        assert!(crypto::deserialize_fr(&hex::decode(&storage.id).unwrap()).is_ok());
+
+        let other = restore_device_storage("phone", &phrase, "other").unwrap();
+        assert_ne!(other.id, storage.id);
+        assert_ne!(other.sm, storage.sm);
+    }
+
+    #[test]
+    fn test_guardian_recovery() {
+        initialize();
+
+        // Fewer guardians than the threshold, so they cannot act without a device
+        let threshold = 3;
+        let roles = [
+            MemberRole::Device,
+            MemberRole::Device,
+            MemberRole::Device,
+            MemberRole::Guardian,
+            MemberRole::Guardian,
+        ];
+        let (parties, members, shares) = setup_actor_with_roles(threshold, &roles);
+        let group_key = shares[0].pg[0].clone();
+        let stored_phs = |members: &[Member]| -> Vec<PublicShare> {
+            shares[0]
+                .phs
+                .iter()
+                .filter(|ph| members.iter().any(|member| member.id == ph.id))
+                .cloned()
+                .collect()
+        };
+
+        assert!(!shares[0].recovery_only);
+        assert!(shares[3..].iter().all(|share| share.recovery_only));
+        assert!(shares[3].partial_sign(b"message").is_err());
+        assert!(crypto::generate_identity_key_share(&shares[3], "hat:guardian").is_err());
+        let device_json = serde_json::to_value(&members[0]).unwrap();
+        let guardian_json = serde_json::to_value(&members[3]).unwrap();
+        assert!(device_json.get("role").is_none());
+        assert_eq!(guardian_json["role"], "GUARDIAN");
+
+        // Two devices are lost; the survivor and the guardians enroll a new one
+        let device_id = generate_id_hex();
+        let device_keypair = generate_keypair_hex();
+        let guardians = &members[3..];
+        let mut new_members = vec![
+            members[0].clone(),
+            Member {
+                id: device_id.clone(),
+                pm: device_keypair.public_key.clone(),
+                role: MemberRole::Device,
+            },
+        ];
+        new_members.extend_from_slice(guardians);
+
+        assert!(generate_recovery_contribution(&shares[0], threshold, &new_members).is_err());
+        let guardian_contributions: Vec<ReceivedContribution> = parties[3..]
+            .iter()
+            .zip(&shares[3..])
+            .map(|((id, _), share)| ReceivedContribution {
+                sender_id: id.clone(),
+                contribution: generate_recovery_contribution(share, threshold, &new_members)
+                    .expect("Failed to generate recovery contribution"),
+            })
+            .collect();
+        let recovery_share_data = ActorShareData {
+            share_code: "recovery-share".to_string(),
+            subject_actor_id: "subject-actor-001".to_string(),
+            hat_id: "hat-001".to_string(),
+            from_actor_id: "from-actor-001".to_string(),
+            to_actor_id: "to-actor-001".to_string(),
+            owner_actor_id: "owner-actor-001".to_string(),
+        };
+
+        // The guardians alone stay below the threshold
+        let guardian_only_contract = ActorContract {
+            threshold,
+            new_members: new_members.clone(),
+            contributions: guardian_contributions.clone(),
+            actor_share: recovery_share_data.clone(),
+        };
+        assert!(generate_recovered_actor_share(
+            "actor-1",
+            threshold,
+            &stored_phs(guardians),
+            &group_key,
+            &guardian_only_contract,
+            &device_id,
+            &device_keypair.secret_key,
+        )
+        .is_err());
+
+        let mut contributions = vec![ReceivedContribution {
+            sender_id: parties[0].0.clone(),
+            contribution: generate_reshare_contribution(&shares[0], threshold, &new_members)
+                .unwrap(),
+        }];
+        contributions.extend(guardian_contributions);
+        let recovery_contract = ActorContract {
+            threshold,
+            new_members: new_members.clone(),
+            contributions,
+            actor_share: recovery_share_data,
+        };
+
+        let contributor_phs = stored_phs(&[&members[..1], guardians].concat());
+        let recovered = generate_recovered_actor_share(
+            "actor-1",
+            threshold,
+            &contributor_phs,
+            &group_key,
+            &recovery_contract,
+            &device_id,
+            &device_keypair.secret_key,
+        )
+        .expect("Failed to recover actor share");
+        assert_eq!(recovered.pg[0], group_key);
+        assert!(!recovered.recovery_only);
+
+        let partial = recovered.partial_sign(b"message").unwrap();
+        let device_ph = recovered.phs.iter().find(|ph| ph.id == device_id).unwrap();
+        assert!(verify_partial(&partial, device_ph, b"message").is_ok());
+
+        let guardian_share = generate_reshared_actor_share(
+            "actor-1",
+            threshold,
+            &contributor_phs,
+            &recovery_contract,
+            &parties[3].0,
+            &parties[3].1.secret_key,
+        )
+        .unwrap();
+        assert!(guardian_share.recovery_only);
+
+        let other_key = generate_keypair_hex().public_key;
+        assert!(generate_recovered_actor_share(
+            "actor-1",
+            threshold,
+            &contributor_phs,
+            &other_key,
+            &recovery_contract,
+            &device_id,
+            &device_keypair.secret_key,
+        )
+        .is_err());
+
+        assert!(generate_recovered_actor_share(
+            "actor-1",
+            threshold,
+            &stored_phs(&members[1..3]),
+            &group_key,
+            &recovery_contract,
+            &device_id,
+            &device_keypair.secret_key,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_device_key_rotation() {
+        initialize();
+
+        let mut storage = generate_device_storage("rotating");
+        let initial_pm = storage.pm.clone();
+        let initial_shared_pm = storage.shared_device_data.pm.clone();
+
+        let first = rotate_device_keys(&mut storage, false).unwrap();
+        assert_eq!(first.len(), 1);
+        assert_eq!(storage.shared_device_data.pm, initial_shared_pm);
+        let second = rotate_device_keys(&mut storage, true).unwrap();
+        assert_eq!(second.len(), 2);
+        assert_eq!(storage.key_version, 2);
+        assert_eq!(storage.shared_device_data.key_version, 1);
+
+        let chain = vec![first[0].clone(), second[0].clone()];
+        assert_eq!(chain[1].counter, 2);
+        assert_eq!(
+            verify_rotation_chain(&storage.id, &initial_pm, &chain).unwrap(),
+            storage.pm
+        );
+        assert_eq!(
+            verify_rotation_chain(
+                &storage.shared_device_data.id,
+                &initial_shared_pm,
+                &second[1..]
+            )
+            .unwrap(),
+            storage.shared_device_data.pm
+        );
+        let signature = crypto::sign(b"rotated", &storage.sm).unwrap();
+        assert!(crypto::verify(b"rotated", &signature, &chain[1].new_pm).unwrap());
+
+        assert!(verify_rotation_chain(&storage.id, &initial_pm, &chain[1..]).is_err());
+        let shared_id = &storage.shared_device_data.id;
+        assert!(verify_rotation_chain(shared_id, &initial_pm, &chain).is_err());
+        let repeated = vec![chain[0].clone(), chain[0].clone()];
+        assert!(verify_rotation_chain(&storage.id, &initial_pm, &repeated).is_err());
+
+        let mut forged = chain[0].clone();
+        forged.timestamp += 1;
+        assert!(verify_rotation_certificate(&forged).is_err());
+        let mut hijacked = chain[0].clone();
+        let attacker = generate_keypair_hex();
+        hijacked.new_pm = attacker.public_key;
+        assert!(verify_rotation_certificate(&hijacked).is_err());
+    }
+
+    #[test]
+    fn test_virtual_device() {
+        initialize();
+
+        let mut shared = VirtualDevice::new("phone");
+        let mut device = VirtualDevice::new("laptop");
+        let outsider = VirtualDevice::new("tablet");
+        let members = vec![
+            Member {
+                id: shared.storage.shared_device_data.id.clone(),
+                pm: shared.storage.shared_device_data.pm.clone(),
+                role: MemberRole::Device,
+            },
+            Member {
+                id: device.storage.id.clone(),
+                pm: device.storage.pm.clone(),
+                role: MemberRole::Device,
+            },
+        ];
+        let mut contract = ActorContract {
+            threshold: 2,
+            new_members: members,
+            contributions: Vec::new(),
+            actor_share: ActorShareData {
+                share_code: shared.registration_share_code(),
+                subject_actor_id: "subject-actor-001".to_string(),
+                hat_id: "hat-001".to_string(),
+                from_actor_id: "from-actor-001".to_string(),
+                to_actor_id: "to-actor-001".to_string(),
+                owner_actor_id: "owner-actor-001".to_string(),
+            },
+        };
+        for (sender, vd) in [(0, &shared), (1, &device)] {
+            contract.contributions.push(ReceivedContribution {
+                sender_id: contract.new_members[sender].id.clone(),
+                contribution: vd.generate_contribution(&contract).unwrap(),
+            });
+        }
+
+        let share_a = shared.generate_actor_share("actor-1", &contract).unwrap();
+        let share_b = device.generate_actor_share("actor-1", &contract).unwrap();
+        assert_eq!(share_a.member_id, shared.storage.shared_device_data.id);
+        assert_eq!(share_b.member_id, device.storage.id);
+        assert_eq!(share_a.pg, share_b.pg);
+        assert!(outsider
+            .clone()
+            .generate_actor_share("actor-1", &contract)
+            .is_err());
+
+        let code = format!("DEVICEREG_{}", shared.storage.id);
+        assert_eq!(
+            shared.storage.shared_device_data.actor_shares,
+            vec![code.clone()]
+        );
+        assert_eq!(
+            shared.get_share_code("subject-actor-001", Some("hat-001")),
+            Some(code.clone())
+        );
+        assert_eq!(shared.get_share_code("subject-actor-001", None), None);
+        shared.current_hat = Some(HatDescriptor {
+            hat_actor_id: "hat-001".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(
+            shared.get_share_code("subject-actor-001", None),
+            Some(code.clone())
+        );
+
+        let message = b"virtual device";
+        let partials = vec![
+            shared.partial_sign(&code, message).unwrap(),
+            device.partial_sign(&code, message).unwrap(),
+        ];
+        let signature = combine_partial_signatures(contract.threshold, &partials).unwrap();
+        assert!(crypto::verify(message, &signature, &share_a.pg[0]).unwrap());
+        assert!(shared.partial_sign("unknown", message).is_err());
+
+        let signature = shared.sign_shared(message).unwrap();
+        assert!(
+            crypto::verify(message, &signature, &shared.storage.shared_device_data.pm).unwrap()
+        );
+
+        assert!(shared.get_bearer_token(true).is_err());
+        assert_eq!(shared.get_bearer_token(false).unwrap(), "");
+        shared.access_token = Some("token".to_string());
+        assert_eq!(shared.get_bearer_token(true).unwrap(), "Bearer token");
+
+        let restored = VirtualDevice::from_stored(shared.to_stored());
+        assert_eq!(restored.actor_share(&code).unwrap().sh, share_a.sh);
+
+        let mut legacy = shared.to_stored();
+        legacy.actor_shares[0].member_id.clear();
+        legacy.actor_shares[0].phs.clear();
+        let restored_legacy = VirtualDevice::from_stored(legacy);
+        assert_eq!(
+            restored_legacy.actor_share(&code).unwrap().member_id,
+            shared.storage.shared_device_data.id
+        );
+        let partial = restored_legacy.partial_sign(&code, message).unwrap();
+        assert_eq!(partial.id, partials[0].id);
+        assert_eq!(partial.sig, partials[0].sig);
+        assert_eq!(restored.storage.shared_device_data.actor_shares, vec![code]);
+    }
 }
@@ -0,0 +1,383 @@
+use clap::{Args, Parser, Subcommand};
+use security::crypto::utils::{bytes_to_hex, hex_to_bytes};
+use security::crypto::{
+    deserialize_fr, deserialize_g2, get_g2_generator, pvsh_decode_g2, pvsh_encode_g2,
+    pvsh_verify_g2, serialize_fr, sign, verify,
+};
+use security::{
+    generate_actor_share, generate_contribution, generate_device_storage, generate_id_hex,
+    generate_keypair_hex, init_bls, ActorContract, Member,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(
+    name = "security",
+    version,
+    about = "BLS threshold key and share operations"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// Secrets are never taken from the command line, where other users can see
+/// them in the process list.
+#[derive(Args)]
+struct SecretKey {
+    /// File holding the hex secret key, `-` for stdin; defaults to $SECURITY_SECRET_KEY
+    #[arg(long, value_name = "FILE")]
+    secret_key_file: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generate a BLS key pair
+    Keygen,
+    /// Generate a random member id
+    IdGen,
+    /// Device storage operations
+    #[command(subcommand)]
+    Device(DeviceCommand),
+    /// Sign a file (or stdin) with a secret key
+    Sign {
+        #[command(flatten)]
+        secret_key: SecretKey,
+        /// Input file, `-` or omitted for stdin
+        input: Option<PathBuf>,
+    },
+    /// Verify a signature over a file (or stdin); exits with 1 if invalid
+    Verify {
+        #[arg(long)]
+        signature: String,
+        #[arg(long)]
+        public_key: String,
+        /// Input file, `-` or omitted for stdin
+        input: Option<PathBuf>,
+    },
+    /// Generate a contribution for a members JSON array
+    Contribute {
+        /// Members JSON file, `-` for stdin
+        #[arg(long)]
+        members: PathBuf,
+        #[arg(long)]
+        threshold: usize,
+    },
+    /// Compute this member's actor share from an ActorContract JSON
+    ActorShare {
+        /// ActorContract JSON file, `-` for stdin
+        #[arg(long)]
+        contract: PathBuf,
+        #[arg(long)]
+        actor_id: String,
+        #[arg(long)]
+        id: String,
+        #[command(flatten)]
+        secret_key: SecretKey,
+    },
+    /// Publicly verifiable secret sharing operations
+    #[command(subcommand)]
+    Pvsh(PvshCommand),
+}
+
+#[derive(Subcommand)]
+enum DeviceCommand {
+    /// Create a new virtual device storage
+    Create {
+        #[arg(long)]
+        name: String,
+        /// Output file; stdout if omitted
+        #[arg(long)]
+        out: Option<PathBuf>,
+        /// Overwrite an existing output file
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum PvshCommand {
+    /// Encrypt a share for a receiver
+    Encode {
+        #[arg(long)]
+        receiver_id: String,
+        #[arg(long)]
+        receiver_pk: String,
+        /// File holding the hex secret to share, `-` for stdin; defaults to $SECURITY_SECRET
+        #[arg(long, value_name = "FILE")]
+        secret_file: Option<PathBuf>,
+    },
+    /// Check an encrypted share against its public share; exits with 1 if invalid
+    Verify {
+        #[arg(long)]
+        receiver_id: String,
+        #[arg(long)]
+        receiver_pk: String,
+        #[arg(long)]
+        public: String,
+        #[arg(long)]
+        esh: String,
+    },
+    /// Decrypt a share with the receiver's secret key
+    Decode {
+        #[arg(long)]
+        receiver_id: String,
+        #[arg(long)]
+        receiver_pk: String,
+        #[command(flatten)]
+        secret_key: SecretKey,
+        #[arg(long)]
+        esh: String,
+    },
+}
+
+enum Failure {
+    Invalid,
+    Usage(String),
+    Input(String),
+    Crypto(String),
+}
+
+impl Failure {
+    fn exit_code(&self) -> u8 {
+        match self {
+            Failure::Invalid => 1,
+            Failure::Usage(_) => 2,
+            Failure::Input(_) => 3,
+            Failure::Crypto(_) => 4,
+        }
+    }
+}
+
+fn crypto<T>(result: Result<T, String>) -> Result<T, Failure> {
+    result.map_err(Failure::Crypto)
+}
+
+fn read_input(path: Option<&Path>) -> Result<Vec<u8>, Failure> {
+    match path {
+        Some(path) if path != Path::new("-") => fs::read(path)
+            .map_err(|e| Failure::Input(format!("Failed to read {}: {}", path.display(), e))),
+        _ => {
+            let mut data = Vec::new();
+            io::stdin()
+                .read_to_end(&mut data)
+                .map_err(|e| Failure::Input(format!("Failed to read stdin: {}", e)))?;
+            Ok(data)
+        }
+    }
+}
+
+fn is_stdin(path: Option<&Path>) -> bool {
+    path.is_none_or(|path| path == Path::new("-"))
+}
+
+fn read_secret(file: Option<&Path>, var: &str, flag: &str) -> Result<String, Failure> {
+    let secret = match file {
+        Some(file) => String::from_utf8(read_input(Some(file))?)
+            .map_err(|_| Failure::Input(format!("{} is not valid UTF-8", file.display())))?,
+        None => {
+            env::var(var).map_err(|_| Failure::Usage(format!("pass --{} or set {}", flag, var)))?
+        }
+    };
+    Ok(secret.trim().to_string())
+}
+
+impl SecretKey {
+    fn reads_stdin(&self) -> bool {
+        self.secret_key_file.as_deref() == Some(Path::new("-"))
+    }
+
+    fn read(&self) -> Result<String, Failure> {
+        read_secret(
+            self.secret_key_file.as_deref(),
+            "SECURITY_SECRET_KEY",
+            "secret-key-file",
+        )
+    }
+}
+
+fn write_private(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        // The mode only applies to new files; --force may be replacing one
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(contents)
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T, Failure> {
+    let data = read_input(Some(path))?;
+    serde_json::from_slice(&data)
+        .map_err(|e| Failure::Input(format!("Invalid JSON in {}: {}", path.display(), e)))
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<(), Failure> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| Failure::Input(format!("Failed to encode output: {}", e)))?;
+    println!("{}", json);
+    Ok(())
+}
+
+fn run(command: Command) -> Result<(), Failure> {
+    match command {
+        Command::Keygen => print_json(&generate_keypair_hex()),
+        Command::IdGen => print_json(&generate_id_hex()),
+        Command::Device(DeviceCommand::Create { name, out, force }) => {
+            let storage = generate_device_storage(&name);
+            match out {
+                Some(out) => {
+                    if out.exists() && !force {
+                        return Err(Failure::Input(format!(
+                            "{} already exists (use --force to overwrite)",
+                            out.display()
+                        )));
+                    }
+                    let json = serde_json::to_string_pretty(&storage)
+                        .map_err(|e| Failure::Input(format!("Failed to encode device: {}", e)))?;
+                    write_private(&out, json.as_bytes()).map_err(|e| {
+                        Failure::Input(format!("Failed to write {}: {}", out.display(), e))
+                    })?;
+                    print_json(&storage.id)
+                }
+                None => print_json(&storage),
+            }
+        }
+        Command::Sign { secret_key, input } => {
+            if secret_key.reads_stdin() && is_stdin(input.as_deref()) {
+                return Err(Failure::Usage(
+                    "the secret key and the input cannot both come from stdin".to_string(),
+                ));
+            }
+            let secret_key = secret_key.read()?;
+            let data = read_input(input.as_deref())?;
+            print_json(&crypto(sign(&data, &secret_key))?)
+        }
+        Command::Verify {
+            signature,
+            public_key,
+            input,
+        } => {
+            let data = read_input(input.as_deref())?;
+            let valid = crypto(verify(&data, &signature, &public_key))?;
+            print_json(&valid)?;
+            if valid {
+                Ok(())
+            } else {
+                Err(Failure::Invalid)
+            }
+        }
+        Command::Contribute { members, threshold } => {
+            let members: Vec<Member> = read_json(&members)?;
+            print_json(&crypto(generate_contribution(threshold, &members))?)
+        }
+        Command::ActorShare {
+            contract,
+            actor_id,
+            id,
+            secret_key,
+        } => {
+            if secret_key.reads_stdin() && is_stdin(Some(&contract)) {
+                return Err(Failure::Usage(
+                    "the secret key and the contract cannot both come from stdin".to_string(),
+                ));
+            }
+            let secret_key = secret_key.read()?;
+            let contract: ActorContract = read_json(&contract)?;
+            print_json(&crypto(generate_actor_share(
+                &actor_id,
+                &contract,
+                &id,
+                &secret_key,
+            ))?)
+        }
+        Command::Pvsh(command) => run_pvsh(command),
+    }
+}
+
+fn run_pvsh(command: PvshCommand) -> Result<(), Failure> {
+    let fr = |hex: &str| crypto(hex_to_bytes(hex).and_then(|bytes| deserialize_fr(&bytes)));
+    let g2 = |hex: &str| crypto(hex_to_bytes(hex).and_then(|bytes| deserialize_g2(&bytes)));
+
+    match command {
+        PvshCommand::Encode {
+            receiver_id,
+            receiver_pk,
+            secret_file,
+        } => {
+            let secret = read_secret(secret_file.as_deref(), "SECURITY_SECRET", "secret-file")?;
+            let esh = crypto(pvsh_encode_g2(
+                &fr(&receiver_id)?,
+                &g2(&receiver_pk)?,
+                &fr(&secret)?,
+                &get_g2_generator(),
+            ))?;
+            print_json(&esh)
+        }
+        PvshCommand::Verify {
+            receiver_id,
+            receiver_pk,
+            public,
+            esh,
+        } => {
+            let valid = pvsh_verify_g2(
+                &fr(&receiver_id)?,
+                &g2(&receiver_pk)?,
+                &g2(&public)?,
+                &esh,
+                &get_g2_generator(),
+            )
+            .is_ok();
+            print_json(&valid)?;
+            if valid {
+                Ok(())
+            } else {
+                Err(Failure::Invalid)
+            }
+        }
+        PvshCommand::Decode {
+            receiver_id,
+            receiver_pk,
+            secret_key,
+            esh,
+        } => {
+            let secret_key = secret_key.read()?;
+            let sh = crypto(pvsh_decode_g2(
+                &fr(&receiver_id)?,
+                &g2(&receiver_pk)?,
+                &fr(&secret_key)?,
+                &esh,
+            ))?;
+            print_json(&bytes_to_hex(&serialize_fr(&sh)))
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    init_bls();
+
+    match run(cli.command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(failure) => {
+            match &failure {
+                Failure::Invalid => {}
+                Failure::Usage(message) | Failure::Input(message) | Failure::Crypto(message) => {
+                    eprintln!("error: {}", message)
+                }
+            }
+            ExitCode::from(failure.exit_code())
+        }
+    }
+}
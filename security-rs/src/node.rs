@@ -0,0 +1,417 @@
+use crate::crypto::threshold::{generate_actor_share, generate_contribution};
+use crate::crypto::utils::{bytes_to_hex, hex_to_bytes};
+use crate::crypto::{
+    decrypt_ecies, derive_public_key_g2, deserialize_fr, deserialize_g2, encrypt_ecies,
+    generate_id_hex, generate_keypair_hex, get_g2_generator, init_bls, pvsh_decode_g2,
+    pvsh_encode_g2, pvsh_verify_g2, serialize_fr, serialize_g2, sign, verify,
+};
+use crate::device::generate_device_storage as generate_storage;
+use crate::types;
+use napi::bindgen_prelude::{Buffer, Either, Null};
+use napi::{Error, Result};
+use napi_derive::napi;
+use std::sync::Once;
+
+static INIT: Once = Once::new();
+
+fn ensure_initialized() {
+    INIT.call_once(init_bls);
+}
+
+fn nullable_string(value: &Option<Either<String, Null>>) -> String {
+    match value {
+        Some(Either::A(value)) => value.clone(),
+        _ => String::new(),
+    }
+}
+
+fn to_napi<T>(result: std::result::Result<T, String>) -> Result<T> {
+    result.map_err(Error::from_reason)
+}
+
+#[napi(object, js_name = "KeyPair")]
+pub struct JsKeyPair {
+    pub secret_key: String,
+    pub public_key: String,
+}
+
+#[napi(object, js_name = "IMember")]
+pub struct JsMember {
+    pub id: String,
+    pub pm: String,
+    pub role: Option<String>,
+}
+
+#[napi(object, js_name = "IESH")]
+pub struct JsEncryptedShare {
+    pub receiver_id: String,
+    #[napi(js_name = "receiverPK")]
+    pub receiver_pk: String,
+    pub esh: String,
+}
+
+#[napi(object, js_name = "IContribution")]
+pub struct JsContribution {
+    pub pg: Vec<String>,
+    pub esh: Vec<JsEncryptedShare>,
+}
+
+#[napi(object, js_name = "IReceivedContribution")]
+pub struct JsReceivedContribution {
+    pub sender_id: String,
+    pub contribution: JsContribution,
+}
+
+#[napi(object, js_name = "IActorShareData")]
+pub struct JsActorShareData {
+    pub share_code: String,
+    pub subject_actor_id: String,
+    pub subject_actor_type: Option<String>,
+    pub hat_id: Option<Either<String, Null>>,
+    pub from_actor_id: Option<Either<String, Null>>,
+    pub role_code: Option<String>,
+    pub to_actor_id: String,
+    pub owner_actor_id: Option<String>,
+}
+
+#[napi(object, js_name = "IActorContract")]
+pub struct JsActorContract {
+    pub threshold: u32,
+    pub new_members: Vec<JsMember>,
+    pub contributions: Vec<JsReceivedContribution>,
+    pub actor_share: JsActorShareData,
+}
+
+#[napi(object, js_name = "IPublicShare")]
+pub struct JsPublicShare {
+    pub id: String,
+    pub ph: String,
+}
+
+#[napi(object, js_name = "IActorShareDataDevice")]
+pub struct JsActorShareDataDevice {
+    pub actor_id: String,
+    pub share_code: String,
+    pub subject_actor_id: String,
+    pub subject_actor_type: Option<String>,
+    pub hat_id: Option<Either<String, Null>>,
+    pub from_actor_id: Option<Either<String, Null>>,
+    pub role_code: Option<String>,
+    pub to_actor_id: String,
+    pub owner_actor_id: Option<String>,
+    pub pg: String,
+    pub pgs: Vec<String>,
+    pub sh: String,
+    pub ph: String,
+    pub phs: Vec<JsPublicShare>,
+}
+
+#[napi(object, js_name = "SharedDeviceData")]
+pub struct JsSharedDeviceData {
+    pub id: String,
+    pub sm: String,
+    pub pm: String,
+    pub actor_shares: Vec<JsActorShareDataDevice>,
+}
+
+#[napi(object, js_name = "VirtualDeviceStorage")]
+pub struct JsVirtualDeviceStorage {
+    pub id: String,
+    pub sm: String,
+    pub pm: String,
+    pub name: String,
+    pub shared_device_data: JsSharedDeviceData,
+}
+
+#[napi(object, js_name = "IEncryptedData")]
+pub struct JsEncryptedData {
+    pub iv: String,
+    pub ephemeral_public_key: String,
+    pub cipher_data: String,
+    pub mac: String,
+}
+
+impl TryFrom<JsMember> for types::Member {
+    type Error = Error;
+
+    fn try_from(member: JsMember) -> Result<Self> {
+        let role = match member.role.as_deref() {
+            None | Some("DEVICE") => types::MemberRole::Device,
+            Some("GUARDIAN") => types::MemberRole::Guardian,
+            Some(role) => return Err(Error::from_reason(format!("Unknown member role: {}", role))),
+        };
+        Ok(types::Member {
+            id: member.id,
+            pm: member.pm,
+            role,
+        })
+    }
+}
+
+impl From<types::Contribution> for JsContribution {
+    fn from(contribution: types::Contribution) -> Self {
+        JsContribution {
+            pg: contribution.pg,
+            esh: contribution
+                .esh
+                .into_iter()
+                .map(|esh| JsEncryptedShare {
+                    receiver_id: esh.receiver_id,
+                    receiver_pk: esh.receiver_pk,
+                    esh: esh.esh,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<JsContribution> for types::Contribution {
+    fn from(contribution: JsContribution) -> Self {
+        types::Contribution {
+            pg: contribution.pg,
+            esh: contribution
+                .esh
+                .into_iter()
+                .map(|esh| types::EncryptedShare {
+                    receiver_id: esh.receiver_id,
+                    receiver_pk: esh.receiver_pk,
+                    esh: esh.esh,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<&JsActorShareData> for types::ActorShareData {
+    fn from(data: &JsActorShareData) -> Self {
+        types::ActorShareData {
+            share_code: data.share_code.clone(),
+            subject_actor_id: data.subject_actor_id.clone(),
+            hat_id: nullable_string(&data.hat_id),
+            from_actor_id: nullable_string(&data.from_actor_id),
+            to_actor_id: data.to_actor_id.clone(),
+            owner_actor_id: data.owner_actor_id.clone().unwrap_or_default(),
+        }
+    }
+}
+
+// Returns the actor share data alongside, since callers echo it back.
+fn actor_contract_from_js(
+    actor_contract: JsActorContract,
+) -> Result<(types::ActorContract, JsActorShareData)> {
+    let actor_share = actor_contract.actor_share;
+    let contract = types::ActorContract {
+        threshold: actor_contract.threshold as usize,
+        actor_share: (&actor_share).into(),
+        new_members: actor_contract
+            .new_members
+            .into_iter()
+            .map(types::Member::try_from)
+            .collect::<Result<_>>()?,
+        contributions: actor_contract
+            .contributions
+            .into_iter()
+            .map(|received| types::ReceivedContribution {
+                sender_id: received.sender_id,
+                contribution: received.contribution.into(),
+            })
+            .collect(),
+    };
+    Ok((contract, actor_share))
+}
+
+impl From<types::EncryptedData> for JsEncryptedData {
+    fn from(encrypted: types::EncryptedData) -> Self {
+        JsEncryptedData {
+            iv: encrypted.iv,
+            ephemeral_public_key: encrypted.ephemeral_public_key,
+            cipher_data: encrypted.cipher_data,
+            mac: encrypted.mac,
+        }
+    }
+}
+
+impl From<JsEncryptedData> for types::EncryptedData {
+    fn from(encrypted: JsEncryptedData) -> Self {
+        types::EncryptedData {
+            iv: encrypted.iv,
+            ephemeral_public_key: encrypted.ephemeral_public_key,
+            cipher_data: encrypted.cipher_data,
+            mac: encrypted.mac,
+        }
+    }
+}
+
+#[napi]
+pub fn generate_key_pair_hex() -> JsKeyPair {
+    ensure_initialized();
+    let keypair = generate_keypair_hex();
+    JsKeyPair {
+        secret_key: keypair.secret_key,
+        public_key: keypair.public_key,
+    }
+}
+
+#[napi]
+pub fn generate_id() -> String {
+    ensure_initialized();
+    generate_id_hex()
+}
+
+#[napi]
+pub fn generate_public_key(secret_key: String) -> Result<String> {
+    ensure_initialized();
+    let sk = to_napi(hex_to_bytes(&secret_key).and_then(|bytes| deserialize_fr(&bytes)))?;
+    Ok(bytes_to_hex(&serialize_g2(&derive_public_key_g2(&sk))))
+}
+
+#[napi]
+pub fn sign_hex(data: String, secret_key: String) -> Result<String> {
+    ensure_initialized();
+    to_napi(hex_to_bytes(&data).and_then(|data| sign(&data, &secret_key)))
+}
+
+#[napi]
+pub fn verify_hex(data: String, signature: String, public_key: String) -> Result<bool> {
+    ensure_initialized();
+    to_napi(hex_to_bytes(&data).and_then(|data| verify(&data, &signature, &public_key)))
+}
+
+#[napi(js_name = "encryptECIES")]
+pub fn encrypt_ecies_buffer(data: Buffer, public_key: String) -> Result<JsEncryptedData> {
+    ensure_initialized();
+    to_napi(encrypt_ecies(&data, &public_key)).map(JsEncryptedData::from)
+}
+
+#[napi(js_name = "decryptECIES")]
+pub fn decrypt_ecies_buffer(encrypted: JsEncryptedData, secret_key: String) -> Result<Buffer> {
+    ensure_initialized();
+    to_napi(decrypt_ecies(&encrypted.into(), &secret_key)).map(Buffer::from)
+}
+
+#[napi]
+pub fn calculate_contribution(threshold: u32, members: Vec<JsMember>) -> Result<JsContribution> {
+    ensure_initialized();
+    let members = members
+        .into_iter()
+        .map(types::Member::try_from)
+        .collect::<Result<Vec<_>>>()?;
+    to_napi(generate_contribution(threshold as usize, &members)).map(JsContribution::from)
+}
+
+#[napi(js_name = "PVSHEncodeG2")]
+pub fn pvsh_encode(id: String, public_key: String, secret: String) -> Result<String> {
+    ensure_initialized();
+    to_napi((|| {
+        let id = deserialize_fr(&hex_to_bytes(&id)?)?;
+        let pk = deserialize_g2(&hex_to_bytes(&public_key)?)?;
+        let sh = deserialize_fr(&hex_to_bytes(&secret)?)?;
+        pvsh_encode_g2(&id, &pk, &sh, &get_g2_generator())
+    })())
+}
+
+#[napi(js_name = "PVSHVerifyG2")]
+pub fn pvsh_verify(id: String, public_key: String, ph: String, esh: String) -> Result<bool> {
+    ensure_initialized();
+    to_napi((|| {
+        let id = deserialize_fr(&hex_to_bytes(&id)?)?;
+        let pk = deserialize_g2(&hex_to_bytes(&public_key)?)?;
+        let ph = deserialize_g2(&hex_to_bytes(&ph)?)?;
+        Ok(pvsh_verify_g2(&id, &pk, &ph, &esh, &get_g2_generator()).is_ok())
+    })())
+}
+
+#[napi(js_name = "PVSHDecodeG2")]
+pub fn pvsh_decode(
+    id: String,
+    public_key: String,
+    secret_key: String,
+    esh: String,
+) -> Result<String> {
+    ensure_initialized();
+    to_napi((|| {
+        let id = deserialize_fr(&hex_to_bytes(&id)?)?;
+        let pk = deserialize_g2(&hex_to_bytes(&public_key)?)?;
+        let sk = deserialize_fr(&hex_to_bytes(&secret_key)?)?;
+        let sh = pvsh_decode_g2(&id, &pk, &sk, &esh)?;
+        Ok(bytes_to_hex(&serialize_fr(&sh)))
+    })())
+}
+
+#[napi]
+pub fn generate_device_storage(device_name: String) -> JsVirtualDeviceStorage {
+    ensure_initialized();
+    let storage = generate_storage(&device_name);
+    JsVirtualDeviceStorage {
+        id: storage.id,
+        sm: storage.sm,
+        pm: storage.pm,
+        name: storage.name,
+        shared_device_data: JsSharedDeviceData {
+            id: storage.shared_device_data.id,
+            sm: storage.shared_device_data.sm,
+            pm: storage.shared_device_data.pm,
+            actor_shares: Vec::new(),
+        },
+    }
+}
+
+#[napi(js_name = "generateContribution")]
+pub fn generate_device_contribution(actor_contract: JsActorContract) -> Result<JsContribution> {
+    ensure_initialized();
+    let (contract, _) = actor_contract_from_js(actor_contract)?;
+    // Contributions already on the contract must deal to the same members
+    // with the same threshold as the one generated here
+    for received in &contract.contributions {
+        let receivers = received.contribution.esh.iter().map(|esh| &esh.receiver_id);
+        if received.contribution.pg.len() != contract.threshold
+            || !receivers.eq(contract.new_members.iter().map(|member| &member.id))
+        {
+            return Err(Error::from_reason(format!(
+                "Contribution from {} does not match the actor contract",
+                received.sender_id
+            )));
+        }
+    }
+    to_napi(generate_contribution(
+        contract.threshold,
+        &contract.new_members,
+    ))
+    .map(JsContribution::from)
+}
+
+#[napi(js_name = "generateActorShare")]
+pub fn generate_device_actor_share(
+    actor_id: String,
+    actor_contract: JsActorContract,
+    id: String,
+    sk: String,
+) -> Result<JsActorShareDataDevice> {
+    ensure_initialized();
+    let (contract, actor_share) = actor_contract_from_js(actor_contract)?;
+
+    let share = to_napi(generate_actor_share(&actor_id, &contract, &id, &sk))?;
+    Ok(JsActorShareDataDevice {
+        actor_id: share.actor_id,
+        share_code: actor_share.share_code,
+        subject_actor_id: actor_share.subject_actor_id,
+        subject_actor_type: actor_share.subject_actor_type,
+        hat_id: actor_share.hat_id,
+        from_actor_id: actor_share.from_actor_id,
+        role_code: actor_share.role_code,
+        to_actor_id: actor_share.to_actor_id,
+        owner_actor_id: actor_share.owner_actor_id,
+        pg: share.pg.first().cloned().unwrap_or_default(),
+        pgs: share.pg,
+        sh: share.sh,
+        ph: share.ph,
+        phs: share
+            .phs
+            .into_iter()
+            .map(|ph| JsPublicShare {
+                id: ph.id,
+                ph: ph.ph,
+            })
+            .collect(),
+    })
+}
@@ -12,6 +12,8 @@ pub struct SharedDeviceData {
     pub sm: String,
     pub pm: String,
     pub actor_shares: Vec<String>,
+    #[serde(default)]
+    pub key_version: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,12 +23,42 @@ pub struct VirtualDeviceStorage {
     pub pm: String,
     pub name: String,
     pub shared_device_data: SharedDeviceData,
+    #[serde(default)]
+    pub key_version: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotationCertificate {
+    pub device_id: String,
+    pub old_pm: String,
+    pub new_pm: String,
+    pub timestamp: u64,
+    pub counter: u64,
+    pub old_signature: String,
+    pub new_signature: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Member {
     pub id: String,
     pub pm: String,
+    #[serde(default, skip_serializing_if = "MemberRole::is_device")]
+    pub role: MemberRole,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum MemberRole {
+    #[default]
+    Device,
+    /// Holds a recovery-only share and can only take part in re-enrolling devices.
+    Guardian,
+}
+
+impl MemberRole {
+    pub fn is_device(&self) -> bool {
+        *self == MemberRole::Device
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,10 +106,44 @@ pub struct ActorContract {
     pub actor_share: ActorShareData,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThresholdKeyError {
+    pub sender_id: Option<String>,
+    pub receiver_id: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ThresholdKeys {
+    #[serde(default)]
     pub actor_id: String,
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub sh: String,
+    #[serde(default)]
+    pub ph: String,
+    pub phs: Vec<PublicShare>,
+    #[serde(rename = "pg")]
+    pub group_key: String,
+    #[serde(rename = "pgs", default)]
     pub pg: Vec<String>,
+    #[serde(default)]
+    pub errors: Vec<ThresholdKeyError>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ThresholdKeyBytes {
+    pub actor_id: String,
+    pub id: Vec<u8>,
+    pub sh: Vec<u8>,
+    pub ph: Vec<u8>,
+    pub phs: Vec<(Vec<u8>, Vec<u8>)>,
+    pub group_key: Vec<u8>,
+    pub pg: Vec<Vec<u8>>,
+    pub errors: Vec<ThresholdKeyError>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -93,4 +159,176 @@ pub struct ActorShare {
     pub sh: String,
     pub ph: String,
     pub phs: Vec<PublicShare>,
+    #[serde(default)]
+    pub epoch: u64,
+    #[serde(default)]
+    pub member_id: String,
+    #[serde(default)]
+    pub recovery_only: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredDevice {
+    pub storage: VirtualDeviceStorage,
+    pub actor_shares: Vec<ActorShare>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialSignature {
+    pub id: String,
+    pub sig: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairPart {
+    pub receiver_id: String,
+    #[serde(rename = "receiverPK")]
+    pub receiver_pk: String,
+    pub ph: String,
+    pub esh: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepairContribution {
+    pub sender_id: String,
+    pub lost_id: String,
+    pub parts: Vec<RepairPart>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepairShare {
+    pub sender_id: String,
+    pub lost_id: String,
+    pub ph: String,
+    pub esh: String,
+    #[serde(default)]
+    pub epoch: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptedData {
+    pub iv: String,
+    pub ephemeral_public_key: String,
+    pub cipher_data: String,
+    pub mac: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DecryptionShare {
+    pub id: String,
+    pub u: String,
+    pub proof: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdentityKeyShare {
+    pub id: String,
+    pub key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BeaconRound {
+    pub actor_id: String,
+    pub round: u64,
+    pub previous_signature: String,
+    pub signature: String,
+    pub randomness: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Eip2335Module {
+    pub function: String,
+    pub params: serde_json::Map<String, serde_json::Value>,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Eip2335Crypto {
+    pub kdf: Eip2335Module,
+    pub checksum: Eip2335Module,
+    pub cipher: Eip2335Module,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Eip2335Keystore {
+    pub crypto: Eip2335Crypto,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub pubkey: String,
+    pub path: String,
+    pub uuid: String,
+    pub version: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HatDescriptor {
+    pub hat_actor_id: String,
+    #[serde(default)]
+    pub hat_actor_name: Option<String>,
+    pub owner_actor_id: String,
+    pub owner_actor_pm: String,
+    #[serde(default)]
+    pub owner_actor_name: Option<String>,
+    pub role_code: String,
+    pub to_actor_id: String,
+    #[serde(default)]
+    pub to_actor_name: Option<String>,
+    pub share_code: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailResponse {
+    pub uid: String,
+    pub email: String,
+    pub is_primary: bool,
+    pub is_verified: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PhoneNumber {
+    pub uid: String,
+    pub country_code: String,
+    pub line_number: String,
+    pub merged: String,
+    pub is_primary: bool,
+    pub is_verified: bool,
+    pub verified_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Lookup {
+    pub code: String,
+    pub display_text: Option<String>,
+    pub state: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UserResponse {
+    pub code: String,
+    #[serde(rename = "type")]
+    pub user_type: String,
+    pub subtype: String,
+    pub method: String,
+    pub submethod: String,
+    pub display_text: String,
+    pub is_company: bool,
+    pub email: EmailResponse,
+    pub phone_number: Option<PhoneNumber>,
+    pub discriminator: String,
+    pub missions: Vec<String>,
+    pub language: Lookup,
+    pub state: String,
+    pub act_user_id_proc: Lookup,
+    pub referee_code: String,
+    pub referrer_code: String,
+    pub distributor_code: String,
 }
@@ -0,0 +1,198 @@
+use crate::crypto::sign;
+use crate::crypto::threshold::{
+    compute_public_shares, generate_actor_share, generate_contribution,
+};
+use crate::device::generate_device_storage;
+use crate::keystore::{self, KeystoreError};
+use crate::types::{
+    ActorContract, ActorShare, Contribution, HatDescriptor, Member, MemberRole, PartialSignature,
+    StoredDevice, UserResponse, VirtualDeviceStorage,
+};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Rust counterpart of the TS `VirtualDevice`: owns the device storage and
+/// the typed actor shares it holds, keyed by share code.
+#[derive(Debug, Clone)]
+pub struct VirtualDevice {
+    pub storage: VirtualDeviceStorage,
+    pub access_token: Option<String>,
+    pub current_profile: Option<UserResponse>,
+    pub current_hat: Option<HatDescriptor>,
+    pub api_version: Option<String>,
+    actor_shares: BTreeMap<String, ActorShare>,
+}
+
+impl VirtualDevice {
+    pub fn new(device_name: &str) -> Self {
+        Self::from_storage(generate_device_storage(device_name))
+    }
+
+    pub fn from_storage(storage: VirtualDeviceStorage) -> Self {
+        VirtualDevice {
+            storage,
+            access_token: None,
+            current_profile: None,
+            current_hat: None,
+            api_version: None,
+            actor_shares: BTreeMap::new(),
+        }
+    }
+
+    pub fn from_stored(stored: StoredDevice) -> Self {
+        let mut device = Self::from_storage(stored.storage);
+        for share in stored.actor_shares {
+            device.insert_actor_share(share);
+        }
+        device
+    }
+
+    pub fn to_stored(&self) -> StoredDevice {
+        StoredDevice {
+            storage: self.storage.clone(),
+            actor_shares: self.actor_shares.values().cloned().collect(),
+        }
+    }
+
+    pub fn load(path: &Path, password: &str) -> Result<Self, KeystoreError> {
+        keystore::load_device(path, password).map(Self::from_stored)
+    }
+
+    pub fn save(&self, path: &Path, password: &str) -> Result<(), KeystoreError> {
+        let stored = self.to_stored();
+        keystore::save_device(path, &stored.storage, &stored.actor_shares, password)
+    }
+
+    pub fn device_name(&self) -> &str {
+        &self.storage.name
+    }
+
+    /// Stores a share under its share code, replacing any previous one.
+    /// Shares stored without a `member_id` get it filled in from this
+    /// device's ids.
+    pub fn insert_actor_share(&mut self, mut share: ActorShare) {
+        if share.member_id.is_empty() {
+            share.member_id = self.share_member_id(&share).unwrap_or_default();
+        }
+        let codes = &mut self.storage.shared_device_data.actor_shares;
+        if !codes.contains(&share.share_code) {
+            codes.push(share.share_code.clone());
+        }
+        self.actor_shares.insert(share.share_code.clone(), share);
+    }
+
+    fn share_member_id(&self, share: &ActorShare) -> Option<String> {
+        if let Ok(id) = share.resolved_member_id() {
+            return Some(id.to_string());
+        }
+        [&self.storage.shared_device_data.id, &self.storage.id]
+            .into_iter()
+            .find(|id| {
+                let member = Member {
+                    id: id.to_string(),
+                    pm: String::new(),
+                    role: MemberRole::Device,
+                };
+                compute_public_shares(&share.pg, &[member])
+                    .is_ok_and(|phs| phs[0].ph.eq_ignore_ascii_case(&share.ph))
+            })
+            .cloned()
+    }
+
+    pub fn remove_actor_share(&mut self, share_code: &str) -> Option<ActorShare> {
+        self.storage
+            .shared_device_data
+            .actor_shares
+            .retain(|code| code != share_code);
+        self.actor_shares.remove(share_code)
+    }
+
+    pub fn actor_share(&self, share_code: &str) -> Option<&ActorShare> {
+        self.actor_shares.get(share_code)
+    }
+
+    pub fn actor_shares(&self) -> impl Iterator<Item = &ActorShare> {
+        self.actor_shares.values()
+    }
+
+    /// Which of this device's identities (device or shared device) the
+    /// contract addresses, as `(id, secret key)`.
+    fn member_keys(&self, actor_contract: &ActorContract) -> Result<(&str, &str), String> {
+        let shared = &self.storage.shared_device_data;
+        let is_member = |id: &str| actor_contract.new_members.iter().any(|m| m.id == id);
+
+        if is_member(&shared.id) {
+            Ok((&shared.id, &shared.sm))
+        } else if is_member(&self.storage.id) {
+            Ok((&self.storage.id, &self.storage.sm))
+        } else {
+            Err("Device is not a member of the actor contract".to_string())
+        }
+    }
+
+    /// Computes this device's share of the contract and keeps it.
+    pub fn generate_actor_share(
+        &mut self,
+        actor_id: &str,
+        actor_contract: &ActorContract,
+    ) -> Result<ActorShare, String> {
+        let (id, sk) = self.member_keys(actor_contract)?;
+        let share = generate_actor_share(actor_id, actor_contract, id, sk)?;
+        self.insert_actor_share(share.clone());
+        Ok(share)
+    }
+
+    pub fn generate_contribution(
+        &self,
+        actor_contract: &ActorContract,
+    ) -> Result<Contribution, String> {
+        generate_contribution(actor_contract.threshold, &actor_contract.new_members)
+    }
+
+    /// Share code of the share held for `subject_id` under `hat_id`, or under
+    /// the current hat when `hat_id` is not given.
+    pub fn get_share_code(&self, subject_id: &str, hat_id: Option<&str>) -> Option<String> {
+        let hat_id = hat_id
+            .or_else(|| {
+                self.current_hat
+                    .as_ref()
+                    .map(|hat| hat.hat_actor_id.as_str())
+            })
+            .unwrap_or_default();
+
+        self.actor_shares
+            .values()
+            .find(|share| share.subject_actor_id == subject_id && share.hat_id == hat_id)
+            .map(|share| share.share_code.clone())
+    }
+
+    /// Share code the backend expects for this device's registration share.
+    pub fn registration_share_code(&self) -> String {
+        format!("DEVICEREG_{}", self.storage.id)
+    }
+
+    pub fn get_bearer_token(&self, strict: bool) -> Result<String, String> {
+        match &self.access_token {
+            Some(token) => Ok(format!("Bearer {}", token)),
+            None if strict => Err("Access token is not available!".to_string()),
+            None => Ok(String::new()),
+        }
+    }
+
+    /// Signs with the device key.
+    pub fn sign(&self, data: &[u8]) -> Result<String, String> {
+        sign(data, &self.storage.sm)
+    }
+
+    /// Signs with the shared device key, as operation requests require.
+    pub fn sign_shared(&self, data: &[u8]) -> Result<String, String> {
+        sign(data, &self.storage.shared_device_data.sm)
+    }
+
+    /// Partial signature with the actor share stored under `share_code`.
+    pub fn partial_sign(&self, share_code: &str, data: &[u8]) -> Result<PartialSignature, String> {
+        self.actor_share(share_code)
+            .ok_or_else(|| format!("No actor share for {}", share_code))?
+            .partial_sign(data)
+    }
+}
@@ -0,0 +1,307 @@
+use security::api::{
+    register_device, stringify_with_converter, ActorContractResponse, ApiClient, ApiConfig,
+    ApiError, ContributeDevicePayload, CreateDeviceRequest, InitiationContext,
+    InputOperationRequest, OperationRequest, OperationStatus, RegistrationOptions,
+};
+use security::{crypto, generate_contribution, generate_device_storage, init_bls, VirtualDevice};
+use serde_json::{json, Value};
+use std::sync::Once;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use tiny_http::{Header, Response, Server};
+
+static INIT: Once = Once::new();
+
+fn initialize() {
+    INIT.call_once(|| {
+        init_bls();
+    });
+}
+
+struct Recorded {
+    url: String,
+    headers: Vec<(String, String)>,
+    body: Value,
+}
+
+/// Answers `requests` requests with `handler`, after `delay`, and returns
+/// what it received.
+fn serve<F>(requests: usize, delay: Duration, mut handler: F) -> (String, JoinHandle<Vec<Recorded>>)
+where
+    F: FnMut(&Recorded) -> (u16, String) + Send + 'static,
+{
+    let server = Server::http("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}/api", server.server_addr());
+
+    let handle = thread::spawn(move || {
+        let mut recorded = Vec::new();
+        for _ in 0..requests {
+            let mut request = server.recv().unwrap();
+            let mut content = String::new();
+            request.as_reader().read_to_string(&mut content).unwrap();
+            recorded.push(Recorded {
+                url: request.url().to_string(),
+                headers: request
+                    .headers()
+                    .iter()
+                    .map(|h| (h.field.to_string(), h.value.to_string()))
+                    .collect(),
+                body: serde_json::from_str(&content).unwrap_or(Value::Null),
+            });
+
+            let (status, body) = handler(recorded.last().unwrap());
+            thread::sleep(delay);
+            let response = Response::from_string(body)
+                .with_status_code(status)
+                .with_header(Header::from_bytes("Content-Type", "application/json").unwrap());
+            let _ = request.respond(response);
+        }
+        recorded
+    });
+    (base_url, handle)
+}
+
+/// Serves one canned `(status, body)` per request.
+fn serve_canned(
+    responses: Vec<(u16, String)>,
+    delay: Duration,
+) -> (String, JoinHandle<Vec<Recorded>>) {
+    let count = responses.len();
+    let mut responses = responses.into_iter();
+    serve(count, delay, move |_| responses.next().unwrap())
+}
+
+fn header<'a>(recorded: &'a Recorded, name: &str) -> Option<&'a str> {
+    recorded
+        .headers
+        .iter()
+        .find(|(field, _)| field.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+#[test]
+fn test_api_device_registration_requests() {
+    initialize();
+
+    let storage = generate_device_storage("api");
+    let contract = json!({
+        "id": "contract-1",
+        "threshold": 1,
+        "contributors": [],
+        "newMembers": [{ "id": storage.id, "pm": storage.pm }],
+        "contributions": [],
+        "actorShare": {
+            "shareCode": format!("DEVICEREG_{}", storage.id),
+            "subjectActorId": storage.shared_device_data.id,
+            "subjectActorType": "DEVICE",
+            "hatId": null,
+            "fromActorId": null,
+            "roleCode": "DEVICEREG",
+            "toActorId": storage.shared_device_data.id
+        },
+        "isFinalized": false
+    });
+    let operation = |state: &str| {
+        json!({ "uid": "op-1", "state": state, "result": contract, "updatedBy": "x", "createdAt": 1 })
+            .to_string()
+    };
+    let (base_url, server) = serve_canned(
+        vec![(200, operation("PENDING")), (201, operation("SUCCESS"))],
+        Duration::ZERO,
+    );
+
+    let client = ApiClient::new(ApiConfig::new(&base_url).with_header("X-Api-Version", "2"));
+    let request = CreateDeviceRequest {
+        model: "Test".to_string(),
+        ..CreateDeviceRequest::from_storage(&storage)
+    };
+    let created = client.create_device(&request).unwrap();
+    assert_eq!(created.state, OperationStatus::Pending);
+    let created: ActorContractResponse = created.into_result().unwrap();
+    let actor_contract = created.to_actor_contract().unwrap();
+    assert_eq!(actor_contract.new_members[0].id, storage.id);
+    assert_eq!(actor_contract.actor_share.hat_id, "");
+
+    let contribution =
+        generate_contribution(actor_contract.threshold, &actor_contract.new_members).unwrap();
+    let input = InputOperationRequest {
+        data: OperationRequest {
+            payload: ContributeDevicePayload {
+                contract_id: "contract-1".to_string(),
+                sender_id: storage.id.clone(),
+                contribution,
+                device_registration_token: storage.shared_device_data.id.clone(),
+            },
+            initiation_context: InitiationContext {
+                role_code: "DEVICEREG".to_string(),
+                ..Default::default()
+            },
+            message_id: "message-1".to_string(),
+            url: client.contribute_device_url("contract-1"),
+        },
+        signature: "signature".to_string(),
+    };
+    let contributed = client.contribute_device("contract-1", &input).unwrap();
+    assert_eq!(contributed.state, OperationStatus::Success);
+
+    let recorded = server.join().unwrap();
+    assert_eq!(recorded[0].url, "/api/devices");
+    assert_eq!(header(&recorded[0], "X-Api-Version"), Some("2"));
+    assert_eq!(recorded[0].body["sharedId"], storage.shared_device_data.id);
+    assert_eq!(recorded[0].body["osName"], "");
+
+    assert_eq!(
+        recorded[1].url,
+        "/api/actor-contracts/contract-1/contribute-device"
+    );
+    let data = &recorded[1].body["data"];
+    assert_eq!(
+        data["url"],
+        format!("{}/actor-contracts/contract-1/contribute-device", base_url)
+    );
+    assert_eq!(data["messageId"], "message-1");
+    assert_eq!(
+        data["payload"]["deviceRegistrationToken"],
+        storage.shared_device_data.id
+    );
+    assert!(data["payload"]["contribution"]["esh"][0]["receiverPK"].is_string());
+    assert_eq!(data["initiationContext"]["instructionMode"]["code"], "");
+    assert_eq!(recorded[1].body["signature"], "signature");
+}
+
+#[test]
+fn test_api_errors() {
+    initialize();
+
+    let storage = generate_device_storage("api-errors");
+    let request = CreateDeviceRequest::from_storage(&storage);
+    let (base_url, server) = serve_canned(
+        vec![
+            (500, "boom".to_string()),
+            (200, "not json".to_string()),
+            (
+                200,
+                json!({ "uid": "op-2", "state": "EXPIRED" }).to_string(),
+            ),
+        ],
+        Duration::ZERO,
+    );
+    let client = ApiClient::new(ApiConfig::new(&base_url));
+
+    match client.create_device(&request) {
+        Err(ApiError::Status { status, body }) => {
+            assert_eq!(status, 500);
+            assert_eq!(body, "boom");
+        }
+        other => panic!("Expected status error, got {:?}", other),
+    }
+    assert!(matches!(
+        client.create_device(&request),
+        Err(ApiError::Decode(_))
+    ));
+    match client.create_device(&request).unwrap().into_result() {
+        Err(ApiError::Operation { uid, state }) => {
+            assert_eq!(uid, "op-2");
+            assert_eq!(state, OperationStatus::Expired);
+        }
+        other => panic!("Expected operation error, got {:?}", other),
+    }
+    server.join().unwrap();
+
+    let (base_url, server) = serve_canned(vec![(200, "{}".to_string())], Duration::from_secs(2));
+    let client =
+        ApiClient::new(ApiConfig::new(&base_url).with_timeout(Some(Duration::from_millis(200))));
+    assert!(matches!(
+        client.create_device(&request),
+        Err(ApiError::Timeout)
+    ));
+    server.join().unwrap();
+}
+
+#[test]
+fn test_register_device() {
+    initialize();
+
+    let device = VirtualDevice::new("registering");
+    let (id, pm) = (device.storage.id.clone(), device.storage.pm.clone());
+    let mut contribute_attempts = 0;
+    let (base_url, server) = serve(3, Duration::ZERO, move |request| {
+        let mut contract = json!({
+            "id": "contract-7",
+            "threshold": 1,
+            "newMembers": [{ "id": id, "pm": pm }],
+            "contributions": [],
+            "actorShare": { "shareCode": format!("DEVICEREG_{}", id), "roleCode": "DEVICEREG" },
+            "isFinalized": false
+        });
+        if request.url == "/api/devices" {
+            return (
+                201,
+                json!({ "uid": "op-1", "state": "SUCCESS", "result": contract }).to_string(),
+            );
+        }
+
+        contribute_attempts += 1;
+        if contribute_attempts == 1 {
+            return (503, "busy".to_string());
+        }
+        let payload = &request.body["data"]["payload"];
+        contract["contributions"] =
+            json!([{ "senderId": payload["senderId"], "contribution": payload["contribution"] }]);
+        contract["isFinalized"] = json!(true);
+        (
+            200,
+            json!({ "uid": "op-2", "state": "SUCCESS", "result": contract }).to_string(),
+        )
+    });
+
+    let client = ApiClient::new(ApiConfig::new(&base_url));
+    let options = RegistrationOptions {
+        model: "Test".to_string(),
+        message_id: Some("registration-1".to_string()),
+        retry_delay: Duration::from_millis(10),
+        ..Default::default()
+    };
+    let registered = register_device(&client, &device, &options).unwrap();
+    let recorded = server.join().unwrap();
+
+    assert_eq!(registered.operation.uid, "op-2");
+    let code = device.registration_share_code();
+    assert_eq!(
+        registered.device.storage.shared_device_data.actor_shares,
+        vec![code.clone()]
+    );
+    let share = registered.device.actor_share(&code).unwrap();
+    assert_eq!(share.member_id, device.storage.id);
+    assert!(device.actor_share(&code).is_none());
+
+    assert_eq!(recorded[0].body["osName"], "Other");
+    assert_eq!(
+        recorded[1].body, recorded[2].body,
+        "Retries must resend the same operation"
+    );
+    let data = &recorded[2].body["data"];
+    assert_eq!(data["messageId"], "registration-1");
+    assert_eq!(data["initiationContext"]["shareCode"], code.as_str());
+    assert_eq!(
+        data["initiationContext"]["instructionMode"]["code"],
+        "IN_PERSON"
+    );
+    assert_eq!(
+        data["initiationContext"]["initiationRole"]["code"],
+        "OWN_NAME"
+    );
+
+    let signed = stringify_with_converter(data).unwrap();
+    let signature = recorded[2].body["signature"].as_str().unwrap();
+    assert!(crypto::verify(
+        signed.as_bytes(),
+        signature,
+        &device.storage.shared_device_data.pm
+    )
+    .unwrap());
+    assert_eq!(
+        stringify_with_converter(&json!({ "a": 2, "b": [1.5] })).unwrap(),
+        r#"{"a":"2.0","b":["1.5"]}"#
+    );
+}
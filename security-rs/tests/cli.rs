@@ -0,0 +1,288 @@
+use security::{
+    ActorContract, ActorShare, ActorShareData, Contribution, KeyPair, Member, MemberRole,
+    ReceivedContribution, VirtualDeviceStorage,
+};
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+fn security(args: &[&str], stdin: Option<&[u8]>) -> Output {
+    security_with_env(args, stdin, &[])
+}
+
+fn security_with_env(args: &[&str], stdin: Option<&[u8]>, envs: &[(&str, &str)]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_security"))
+        .args(args)
+        .env_remove("SECURITY_SECRET_KEY")
+        .env_remove("SECURITY_SECRET")
+        .envs(envs.iter().copied())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start security");
+    let mut child_stdin = child.stdin.take().unwrap();
+    child_stdin.write_all(stdin.unwrap_or_default()).unwrap();
+    drop(child_stdin);
+    child.wait_with_output().expect("Failed to run security")
+}
+
+fn security_json<T: DeserializeOwned>(args: &[&str], stdin: Option<&[u8]>) -> T {
+    security_json_with_env(args, stdin, &[])
+}
+
+fn security_json_with_env<T: DeserializeOwned>(
+    args: &[&str],
+    stdin: Option<&[u8]>,
+    envs: &[(&str, &str)],
+) -> T {
+    let output = security_with_env(args, stdin, envs);
+    assert!(
+        output.status.success(),
+        "security {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("Output is not valid JSON")
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("security-cli-{}-{}", std::process::id(), name))
+}
+
+#[test]
+fn test_cli_sign_verify() {
+    let keypair: KeyPair = security_json(&["keygen"], None);
+    let message = b"hello from the cli";
+
+    let signature: String = security_json_with_env(
+        &["sign", "-"],
+        Some(message),
+        &[("SECURITY_SECRET_KEY", &keypair.secret_key)],
+    );
+    let key_path = temp_path("sign.key");
+    fs::write(&key_path, format!("{}\n", keypair.secret_key)).unwrap();
+    let message_path = temp_path("sign.msg");
+    fs::write(&message_path, message).unwrap();
+    let from_file: String = security_json(
+        &[
+            "sign",
+            "--secret-key-file",
+            key_path.to_str().unwrap(),
+            message_path.to_str().unwrap(),
+        ],
+        None,
+    );
+    assert_eq!(from_file, signature);
+    let from_stdin: String = security_json(
+        &[
+            "sign",
+            "--secret-key-file",
+            "-",
+            message_path.to_str().unwrap(),
+        ],
+        Some(keypair.secret_key.as_bytes()),
+    );
+    assert_eq!(from_stdin, signature);
+    fs::remove_file(&key_path).unwrap();
+    fs::remove_file(&message_path).unwrap();
+    let valid: bool = security_json(
+        &[
+            "verify",
+            "--signature",
+            &signature,
+            "--public-key",
+            &keypair.public_key,
+        ],
+        Some(message),
+    );
+    assert!(valid);
+
+    let output = security(
+        &[
+            "verify",
+            "--signature",
+            &signature,
+            "--public-key",
+            &keypair.public_key,
+        ],
+        Some(b"tampered"),
+    );
+    assert_eq!(output.status.code(), Some(1));
+
+    let output = security_with_env(&["sign"], Some(message), &[("SECURITY_SECRET_KEY", "zz")]);
+    assert_eq!(output.status.code(), Some(4));
+    let output = security(&["sign"], Some(message));
+    assert_eq!(output.status.code(), Some(2));
+    let output = security(&["sign", "--secret-key-file", "-"], Some(message));
+    assert_eq!(output.status.code(), Some(2));
+    let output = security(
+        &["sign", "--secret-key", &keypair.secret_key],
+        Some(message),
+    );
+    assert_eq!(
+        output.status.code(),
+        Some(2),
+        "Secrets must not be accepted on the command line"
+    );
+}
+
+#[test]
+fn test_cli_device_create() {
+    let path = temp_path("device.json");
+    let _ = fs::remove_file(&path);
+    let out = path.to_str().unwrap();
+
+    let id: String = security_json(&["device", "create", "--name", "cli", "--out", out], None);
+    let storage: VirtualDeviceStorage = serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+    assert_eq!(storage.id, id);
+    assert_eq!(storage.name, "cli");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600, "Device file must be private");
+    }
+
+    let output = security(&["device", "create", "--name", "cli", "--out", out], None);
+    assert_eq!(output.status.code(), Some(3));
+
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_cli_contribute_actor_share() {
+    let threshold = 2;
+    let parties: Vec<(String, KeyPair)> = (0..3)
+        .map(|_| {
+            (
+                security_json(&["id-gen"], None),
+                security_json(&["keygen"], None),
+            )
+        })
+        .collect();
+    let members: Vec<Member> = parties
+        .iter()
+        .map(|(id, keypair)| Member {
+            id: id.clone(),
+            pm: keypair.public_key.clone(),
+            role: MemberRole::Device,
+        })
+        .collect();
+    let members_json = serde_json::to_vec(&members).unwrap();
+
+    let contributions = parties
+        .iter()
+        .map(|(id, _)| ReceivedContribution {
+            sender_id: id.clone(),
+            contribution: security_json::<Contribution>(
+                &["contribute", "--members", "-", "--threshold", "2"],
+                Some(&members_json),
+            ),
+        })
+        .collect();
+
+    let contract = ActorContract {
+        threshold,
+        new_members: members,
+        contributions,
+        actor_share: ActorShareData {
+            share_code: "cli-share".to_string(),
+            subject_actor_id: "subject".to_string(),
+            hat_id: "hat".to_string(),
+            from_actor_id: "from".to_string(),
+            to_actor_id: "to".to_string(),
+            owner_actor_id: "owner".to_string(),
+        },
+    };
+    let path = temp_path("contract.json");
+    fs::write(&path, serde_json::to_vec(&contract).unwrap()).unwrap();
+
+    let shares: Vec<ActorShare> = parties
+        .iter()
+        .map(|(id, keypair)| {
+            security_json(
+                &[
+                    "actor-share",
+                    "--contract",
+                    path.to_str().unwrap(),
+                    "--actor-id",
+                    "actor",
+                    "--id",
+                    id,
+                    "--secret-key-file",
+                    "-",
+                ],
+                Some(keypair.secret_key.as_bytes()),
+            )
+        })
+        .collect();
+    fs::remove_file(&path).unwrap();
+
+    assert!(shares.iter().all(|share| share.pg == shares[0].pg));
+    assert_eq!(shares[0].share_code, "cli-share");
+
+    let output = security(
+        &["contribute", "--members", "-", "--threshold", "2"],
+        Some(b"not json"),
+    );
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn test_cli_pvsh_roundtrip() {
+    let receiver: KeyPair = security_json(&["keygen"], None);
+    let receiver_id: String = security_json(&["id-gen"], None);
+    let secret: KeyPair = security_json(&["keygen"], None);
+
+    let esh: String = security_json(
+        &[
+            "pvsh",
+            "encode",
+            "--receiver-id",
+            &receiver_id,
+            "--receiver-pk",
+            &receiver.public_key,
+            "--secret-file",
+            "-",
+        ],
+        Some(secret.secret_key.as_bytes()),
+    );
+    let valid: bool = security_json(
+        &[
+            "pvsh",
+            "verify",
+            "--receiver-id",
+            &receiver_id,
+            "--receiver-pk",
+            &receiver.public_key,
+            "--public",
+            &secret.public_key,
+            "--esh",
+            &esh,
+        ],
+        None,
+    );
+    assert!(valid);
+
+    let decoded: String = security_json_with_env(
+        &[
+            "pvsh",
+            "decode",
+            "--receiver-id",
+            &receiver_id,
+            "--receiver-pk",
+            &receiver.public_key,
+            "--esh",
+            &esh,
+        ],
+        None,
+        &[("SECURITY_SECRET_KEY", &receiver.secret_key)],
+    );
+    assert_eq!(decoded, secret.secret_key);
+
+    let output = security(&["pvsh", "encode"], None);
+    assert_eq!(output.status.code(), Some(2));
+}
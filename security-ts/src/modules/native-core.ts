@@ -0,0 +1,76 @@
+import { createRequire } from "module";
+
+import type {
+  IActorShareDataDevice,
+  VirtualDeviceStorage,
+} from "../lib/virtual-device.types";
+import type { IActorContract, IContribution } from "../types/common";
+import type { IMember } from "../types/thresher.types";
+import type { HexString } from "../types/utils.types";
+import type { EncryptedData, KeyPair } from "./hiver.types";
+
+// Functions exported by the security-rs N-API addon (`--features napi`).
+export interface NativeCore {
+  generateKeyPairHex(): KeyPair<HexString, HexString>;
+  generateId(): HexString;
+  generatePublicKey(secretKey: HexString): HexString;
+  signHex(data: HexString, secretKey: HexString): HexString;
+  verifyHex(
+    data: HexString,
+    signature: HexString,
+    publicKey: HexString,
+  ): boolean;
+  encryptECIES(data: Buffer, publicKey: HexString): EncryptedData<HexString>;
+  decryptECIES(
+    encrypted: EncryptedData<HexString>,
+    secretKey: HexString,
+  ): Buffer;
+  calculateContribution(
+    threshold: number,
+    members: IMember<HexString, HexString>[],
+  ): IContribution<HexString, HexString>;
+  PVSHEncodeG2(id: HexString, publicKey: HexString, secret: HexString): string;
+  PVSHVerifyG2(
+    id: HexString,
+    publicKey: HexString,
+    ph: HexString,
+    esh: string,
+  ): boolean;
+  PVSHDecodeG2(
+    id: HexString,
+    publicKey: HexString,
+    secretKey: HexString,
+    esh: string,
+  ): HexString;
+  generateDeviceStorage(deviceName: string): VirtualDeviceStorage;
+  generateContribution(
+    actorContract: IActorContract,
+  ): IContribution<HexString, HexString>;
+  generateActorShare(
+    actorId: HexString,
+    actorContract: IActorContract,
+    id: HexString,
+    sk: HexString,
+  ): NativeActorShareData;
+}
+
+// `pg` is only the group public key; `pgs` holds every public coefficient.
+export type NativeActorShareData = IActorShareDataDevice & {
+  pgs: HexString[];
+};
+
+let nativeCore: NativeCore | null | undefined;
+
+// Loads the addon from SECURITY_NATIVE_CORE (path to the built .node file).
+// Returns null when the flag is not set so callers keep using bls-wasm.
+export function loadNativeCore(): NativeCore | null {
+  if (nativeCore !== undefined) {
+    return nativeCore;
+  }
+
+  const path = process.env.SECURITY_NATIVE_CORE;
+  nativeCore = path
+    ? (createRequire(import.meta.url)(path) as NativeCore)
+    : null;
+  return nativeCore;
+}